@@ -9,6 +9,7 @@ use ark_relations::r1cs::ConstraintSynthesizer;
 use ark_relations::r1cs::ConstraintSystemRef;
 use ark_relations::r1cs::SynthesisError;
 
+use crate::gadgets::curves::{FixedBaseMulGadget, InputLayout};
 use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
 use crate::gadgets::public_encryptions::elgamal;
 #[derive(Clone)]
@@ -27,10 +28,24 @@ pub struct ElgamalCircuit<C: CurveGroup, GG: CurveVar<C, C::BaseField>> {
     pub _curve_var: PhantomData<GG>,
 }
 
+impl<C, GG> ElgamalCircuit<C, GG>
+where
+    C: CurveGroup,
+    GG: InputLayout<C>,
+{
+    /// The public inputs for the statement `(pk, ct)`, in the order `generate_constraints` allocates them.
+    pub fn public_inputs(pk: &elgamal::PublicKey<C>, ct: &elgamal::Ciphertext<C>) -> Vec<C::BaseField> {
+        let mut inputs = GG::input_elements(pk);
+        inputs.extend(GG::input_elements(&ct.0));
+        inputs.extend(GG::input_elements(&ct.1));
+        inputs
+    }
+}
+
 impl<C, GG> ConstraintSynthesizer<C::BaseField> for ElgamalCircuit<C, GG>
 where 
     C: CurveGroup,
-    GG: CurveVar<C, C::BaseField>,
+    GG: FixedBaseMulGadget<C>,
     C::BaseField: PrimeField,
 {
     fn generate_constraints(self, cs: ConstraintSystemRef<C::BaseField>) -> Result<(), SynthesisError> {
@@ -58,6 +73,9 @@ pub mod test {
     use ark_bn254::Bn254;
     use ark_crypto_primitives::snark::{CircuitSpecificSetupSNARK, SNARK};
     use ark_groth16::Groth16;
+    use ark_ff::ToConstraintField;
+    use ark_r1cs_std::fields::fp::FpVar;
+    use ark_r1cs_std::groups::curves::short_weierstrass::ProjectiveVar;
     use ark_relations::r1cs::ConstraintSynthesizer;
     use ark_std::{UniformRand, test_rng};
     use rand::{SeedableRng, RngCore};
@@ -119,14 +137,7 @@ pub mod test {
 
         let pvk = Groth16::<Bn254>::process_vk(&vk).unwrap();
 
-        let verify_inputs = [
-            public_key.x,
-            public_key.y,
-            ct.0.x,
-            ct.0.y,
-            ct.1.x,
-            ct.1.y,
-        ];
+        let verify_inputs = ElgamalCircuit::<C, GG>::public_inputs(&public_key, &ct);
 
         let proof = Groth16::<Bn254>::prove(&pk, test_circuit, rng).unwrap();
 
@@ -134,4 +145,64 @@ pub mod test {
             Groth16::<Bn254>::verify_with_processed_vk(&pvk, &verify_inputs, &proof).unwrap(),
         )
     }
+
+    #[test]
+    fn test_public_inputs() {
+        let rng = &mut test_rng();
+
+        let parameters = Enc::setup(rng).unwrap();
+        let (pk, _) = Enc::keygen(&parameters, rng).unwrap();
+        let msg = C::rand(rng).into();
+        let randomness = Randomness::rand(rng);
+        let ct = Enc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+
+        let inputs = ElgamalCircuit::<C, GG>::public_inputs(&pk, &ct);
+        assert_eq!(vec![pk.x, pk.y, ct.0.x, ct.0.y, ct.1.x, ct.1.y], inputs);
+        assert_eq!(vec![parameters.generator.x, parameters.generator.y], parameters.to_field_elements().unwrap());
+
+        // the vector matches the instance assignment of the constraint system, after the leading one
+        let test_circuit: ElgamalCircuit<C, GG> = ElgamalCircuit {
+            g: parameters,
+            pk: Some(pk),
+            ct: Some(ct),
+            m: Some(msg),
+            r: Some(randomness),
+            _curve_var: std::marker::PhantomData,
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        test_circuit.generate_constraints(cs.clone()).unwrap();
+        assert_eq!(inputs, cs.borrow().unwrap().instance_assignment[1..]);
+    }
+
+    #[test]
+    fn test_public_inputs_short_weierstrass() {
+        type C = ark_bn254::G1Projective;
+        type GG = ProjectiveVar<ark_bn254::g1::Config, FpVar<ark_bn254::Fq>>;
+        type Enc = elgamal::ElGamal<C>;
+
+        let rng = &mut test_rng();
+
+        let parameters = Enc::setup(rng).unwrap();
+        let (pk, _) = Enc::keygen(&parameters, rng).unwrap();
+        let msg = C::rand(rng).into();
+        let randomness = Randomness::rand(rng);
+        let ct = Enc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+
+        // `z = 1` for each point, where `to_field_elements` has the infinity flag
+        let inputs = ElgamalCircuit::<C, GG>::public_inputs(&pk, &ct);
+        assert_ne!(pk.to_field_elements().unwrap(), inputs[..3]);
+
+        let test_circuit: ElgamalCircuit<C, GG> = ElgamalCircuit {
+            g: parameters,
+            pk: Some(pk),
+            ct: Some(ct),
+            m: Some(msg),
+            r: Some(randomness),
+            _curve_var: std::marker::PhantomData,
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        test_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(inputs, cs.borrow().unwrap().instance_assignment[1..]);
+    }
 }
@@ -0,0 +1,164 @@
+use std::marker::PhantomData;
+
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_r1cs_std::prelude::AllocVar;
+use ark_r1cs_std::prelude::CurveVar;
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_relations::r1cs::ConstraintSystemRef;
+use ark_relations::r1cs::SynthesisError;
+
+use crate::gadgets::curves::{FixedBaseMulGadget, InputLayout};
+use crate::gadgets::public_encryptions::constraints::AsymmetricDecryptionGadget;
+use crate::gadgets::public_encryptions::elgamal;
+
+// ct decrypts to m under the secret key of pk
+#[derive(Clone)]
+pub struct ElgamalDecryptionCircuit<C: CurveGroup, GG: CurveVar<C, C::BaseField>> {
+    // Constants
+    pub g: elgamal::Parameters<C>,
+
+    // statements
+    pub pk: Option<elgamal::PublicKey<C>>,
+    pub ct: Option<elgamal::Ciphertext<C>>,
+    pub m: Option<elgamal::Plaintext<C>>,
+
+    // witnesses
+    pub sk: Option<elgamal::SecretKey<C>>,
+
+    pub _curve_var: PhantomData<GG>,
+}
+
+impl<C, GG> ElgamalDecryptionCircuit<C, GG>
+where
+    C: CurveGroup,
+    GG: InputLayout<C>,
+{
+    /// The public inputs for the statement `(pk, ct, m)`, in the order `generate_constraints` allocates them.
+    pub fn public_inputs(
+        pk: &elgamal::PublicKey<C>,
+        ct: &elgamal::Ciphertext<C>,
+        m: &elgamal::Plaintext<C>,
+    ) -> Vec<C::BaseField> {
+        let mut inputs = GG::input_elements(pk);
+        inputs.extend(GG::input_elements(&ct.0));
+        inputs.extend(GG::input_elements(&ct.1));
+        inputs.extend(GG::input_elements(m));
+        inputs
+    }
+}
+
+impl<C, GG> ConstraintSynthesizer<C::BaseField> for ElgamalDecryptionCircuit<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+    C::BaseField: PrimeField,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<C::BaseField>) -> Result<(), SynthesisError> {
+        // constants
+        let g = elgamal::constraints::ParametersVar::new_constant(cs.clone(), self.g)?;
+
+        // statements
+        let pk = elgamal::constraints::PublicKeyVar::new_input(cs.clone(), || self.pk.ok_or(SynthesisError::AssignmentMissing))?;
+        let ct = elgamal::constraints::OutputVar::new_input(cs.clone(), || self.ct.ok_or(SynthesisError::AssignmentMissing))?;
+        let m = elgamal::constraints::PlaintextVar::new_input(cs.clone(), || self.m.ok_or(SynthesisError::AssignmentMissing))?;
+
+        // witness
+        let sk = elgamal::constraints::SecretKeyVar::new_witness(cs.clone(), || self.sk.as_ref().ok_or(SynthesisError::AssignmentMissing))?;
+
+        let result_var = elgamal::constraints::ElGamalEncGadget::<C, GG>::decrypt(&g, &ct, &sk, &pk)?;
+
+        result_var.plaintext.enforce_equal(&m.plaintext)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use ark_bn254::Bn254;
+    use ark_crypto_primitives::snark::{CircuitSpecificSetupSNARK, SNARK};
+    use ark_groth16::Groth16;
+    use ark_relations::r1cs::ConstraintSynthesizer;
+    use ark_std::{UniformRand, test_rng};
+    use rand::{SeedableRng, RngCore};
+
+    use crate::gadgets::public_encryptions::elgamal::Randomness;
+    use crate::{circuits::elgamal_decryption::ElgamalDecryptionCircuit, gadgets::public_encryptions::elgamal};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+    type GG = ark_ed_on_bn254::constraints::EdwardsVar;
+
+    type Enc = elgamal::ElGamal<C>;
+
+    #[test]
+    fn test_decryption_circuit() {
+        let rng = &mut test_rng();
+
+        let parameters = Enc::setup(rng).unwrap();
+        let (pk, sk) = Enc::keygen(&parameters, rng).unwrap();
+        let msg = C::rand(rng).into();
+        let randomness = Randomness::rand(rng);
+        let ct = Enc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+
+        let test_circuit: ElgamalDecryptionCircuit<C, GG> = ElgamalDecryptionCircuit {
+            g: parameters,
+            pk: Some(pk),
+            ct: Some(ct),
+            m: Some(msg),
+            sk: Some(sk),
+            _curve_var: std::marker::PhantomData,
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        test_circuit.clone().generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // claiming a different plaintext must fail
+        let wrong_circuit = ElgamalDecryptionCircuit {
+            m: Some(C::rand(rng).into()),
+            ..test_circuit
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        wrong_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_groth16_elgamal_decryption() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameters = Enc::setup(rng).unwrap();
+        let (public_key, secret_key) = Enc::keygen(&parameters, rng).unwrap();
+        let msg = C::rand(rng).into();
+        let randomness = Randomness::rand(rng);
+        let ct = Enc::encrypt(&parameters, &public_key, &msg, &randomness).unwrap();
+
+        let test_circuit: ElgamalDecryptionCircuit<C, GG> = ElgamalDecryptionCircuit {
+            g: parameters,
+            pk: Some(public_key),
+            ct: Some(ct),
+            m: Some(msg),
+            sk: Some(secret_key),
+            _curve_var: std::marker::PhantomData,
+        };
+
+        let (pk, vk) = Groth16::<Bn254>::setup(test_circuit.clone(), rng).unwrap();
+
+        let pvk = Groth16::<Bn254>::process_vk(&vk).unwrap();
+
+        let verify_inputs = ElgamalDecryptionCircuit::<C, GG>::public_inputs(&public_key, &ct, &msg);
+
+        let proof = Groth16::<Bn254>::prove(&pk, test_circuit, rng).unwrap();
+
+        assert!(
+            Groth16::<Bn254>::verify_with_processed_vk(&pvk, &verify_inputs, &proof).unwrap(),
+        );
+
+        // claiming the first ciphertext component as the plaintext
+        let wrong_inputs = ElgamalDecryptionCircuit::<C, GG>::public_inputs(&public_key, &ct, &ct.0);
+        assert!(
+            !Groth16::<Bn254>::verify_with_processed_vk(&pvk, &wrong_inputs, &proof).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::marker::PhantomData;
+
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_r1cs_std::prelude::AllocVar;
+use ark_r1cs_std::prelude::CurveVar;
+use ark_r1cs_std::prelude::EqGadget;
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_relations::r1cs::ConstraintSystemRef;
+use ark_relations::r1cs::SynthesisError;
+
+use crate::gadgets::curves::{FixedBaseMulGadget, InputLayout};
+use crate::gadgets::public_encryptions::elgamal;
+
+// ct_prime = ct + Enc(pk, 0; r)
+#[derive(Clone)]
+pub struct ElgamalRerandomizationCircuit<C: CurveGroup, GG: CurveVar<C, C::BaseField>> {
+    // Constants
+    pub g: elgamal::Parameters<C>,
+
+    // statements
+    pub pk: Option<elgamal::PublicKey<C>>,
+    pub ct: Option<elgamal::Ciphertext<C>>,
+    pub ct_prime: Option<elgamal::Ciphertext<C>>,
+
+    // witnesses
+    pub r: Option<elgamal::Randomness<C>>,
+
+    pub _curve_var: PhantomData<GG>,
+}
+
+impl<C, GG> ElgamalRerandomizationCircuit<C, GG>
+where
+    C: CurveGroup,
+    GG: InputLayout<C>,
+{
+    /// The public inputs for the statement `(pk, ct, ct_prime)`, in the order `generate_constraints` allocates them.
+    pub fn public_inputs(
+        pk: &elgamal::PublicKey<C>,
+        ct: &elgamal::Ciphertext<C>,
+        ct_prime: &elgamal::Ciphertext<C>,
+    ) -> Vec<C::BaseField> {
+        let mut inputs = GG::input_elements(pk);
+        inputs.extend(GG::input_elements(&ct.0));
+        inputs.extend(GG::input_elements(&ct.1));
+        inputs.extend(GG::input_elements(&ct_prime.0));
+        inputs.extend(GG::input_elements(&ct_prime.1));
+        inputs
+    }
+}
+
+impl<C, GG> ConstraintSynthesizer<C::BaseField> for ElgamalRerandomizationCircuit<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+    C::BaseField: PrimeField,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<C::BaseField>) -> Result<(), SynthesisError> {
+        // constants
+        let g = elgamal::constraints::ParametersVar::new_constant(cs.clone(), self.g)?;
+
+        // statements
+        let pk = elgamal::constraints::PublicKeyVar::new_input(cs.clone(), || self.pk.ok_or(SynthesisError::AssignmentMissing))?;
+        let ct = elgamal::constraints::OutputVar::new_input(cs.clone(), || self.ct.ok_or(SynthesisError::AssignmentMissing))?;
+        let ct_prime = elgamal::constraints::OutputVar::new_input(cs.clone(), || self.ct_prime.ok_or(SynthesisError::AssignmentMissing))?;
+
+        // witness
+        let r = elgamal::constraints::RandomnessVar::new_witness(cs.clone(), || self.r.as_ref().ok_or(SynthesisError::AssignmentMissing))?;
+
+        let result_var = elgamal::constraints::ElGamalEncGadget::<C, GG>::rerandomize(&g, &ct, &r, &pk)?;
+
+        result_var.enforce_equal(&ct_prime)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use ark_bn254::Bn254;
+    use ark_crypto_primitives::snark::{CircuitSpecificSetupSNARK, SNARK};
+    use ark_groth16::Groth16;
+    use ark_relations::r1cs::ConstraintSynthesizer;
+    use ark_std::{UniformRand, test_rng};
+    use rand::{SeedableRng, RngCore};
+
+    use crate::gadgets::public_encryptions::elgamal::Randomness;
+    use crate::{circuits::elgamal_rerandomization::ElgamalRerandomizationCircuit, gadgets::public_encryptions::elgamal};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+    type GG = ark_ed_on_bn254::constraints::EdwardsVar;
+
+    type Enc = elgamal::ElGamal<C>;
+
+    #[test]
+    fn test_rerandomization_circuit() {
+        let rng = &mut test_rng();
+
+        let parameters = Enc::setup(rng).unwrap();
+        let (pk, _) = Enc::keygen(&parameters, rng).unwrap();
+        let msg = C::rand(rng).into();
+        let ct = Enc::encrypt(&parameters, &pk, &msg, &Randomness::rand(rng)).unwrap();
+        let r_prime = Randomness::rand(rng);
+        let ct_prime = ct.rerandomize(&parameters, &pk, &r_prime);
+
+        let test_circuit: ElgamalRerandomizationCircuit<C, GG> = ElgamalRerandomizationCircuit {
+            g: parameters.clone(),
+            pk: Some(pk),
+            ct: Some(ct),
+            ct_prime: Some(ct_prime),
+            r: Some(r_prime),
+            _curve_var: std::marker::PhantomData,
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        test_circuit.clone().generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // a fresh encryption of the same plaintext is not a re-randomization under this witness
+        let fresh = Enc::encrypt(&parameters, &pk, &msg, &Randomness::rand(rng)).unwrap();
+        let wrong_circuit = ElgamalRerandomizationCircuit {
+            ct_prime: Some(fresh),
+            ..test_circuit
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        wrong_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_groth16_elgamal_rerandomization() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameters = Enc::setup(rng).unwrap();
+        let (public_key, _) = Enc::keygen(&parameters, rng).unwrap();
+        let msg = C::rand(rng).into();
+        let ct = Enc::encrypt(&parameters, &public_key, &msg, &Randomness::rand(rng)).unwrap();
+        let r_prime = Randomness::rand(rng);
+        let ct_prime = ct.rerandomize(&parameters, &public_key, &r_prime);
+
+        let test_circuit: ElgamalRerandomizationCircuit<C, GG> = ElgamalRerandomizationCircuit {
+            g: parameters,
+            pk: Some(public_key),
+            ct: Some(ct),
+            ct_prime: Some(ct_prime),
+            r: Some(r_prime),
+            _curve_var: std::marker::PhantomData,
+        };
+
+        let (pk, vk) = Groth16::<Bn254>::setup(test_circuit.clone(), rng).unwrap();
+
+        let pvk = Groth16::<Bn254>::process_vk(&vk).unwrap();
+
+        let verify_inputs = ElgamalRerandomizationCircuit::<C, GG>::public_inputs(&public_key, &ct, &ct_prime);
+
+        let proof = Groth16::<Bn254>::prove(&pk, test_circuit, rng).unwrap();
+
+        assert!(
+            Groth16::<Bn254>::verify_with_processed_vk(&pvk, &verify_inputs, &proof).unwrap(),
+        );
+
+        // swapping ct and ct_prime claims the inverse relation, which the proof does not show
+        let swapped_inputs = ElgamalRerandomizationCircuit::<C, GG>::public_inputs(&public_key, &ct_prime, &ct);
+        assert!(
+            !Groth16::<Bn254>::verify_with_processed_vk(&pvk, &swapped_inputs, &proof).unwrap(),
+        );
+    }
+}
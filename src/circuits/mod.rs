@@ -3,3 +3,7 @@ pub mod multiply_v2;
 pub mod if_1;
 pub mod if_2;
 pub mod elgamal;
+pub mod elgamal_decryption;
+pub mod elgamal_rerandomization;
+pub mod pedersen_opening;
+pub mod schnorr_verification;
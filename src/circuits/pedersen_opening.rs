@@ -0,0 +1,200 @@
+use std::marker::PhantomData;
+
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_r1cs_std::prelude::AllocVar;
+use ark_r1cs_std::prelude::AllocationMode;
+use ark_r1cs_std::prelude::CurveVar;
+use ark_r1cs_std::prelude::EqGadget;
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_relations::r1cs::ConstraintSystemRef;
+use ark_relations::r1cs::SynthesisError;
+
+use crate::gadgets::commitments::pedersen;
+use crate::gadgets::curves::{FixedBaseMulGadget, InputLayout};
+
+// commitment = r*H + sum_i values_i*G_i
+#[derive(Clone)]
+pub struct PedersenOpeningCircuit<C: CurveGroup, GG: CurveVar<C, C::BaseField>> {
+    // Constants
+    pub parameters: pedersen::Parameters<C>,
+
+    // statements
+    pub commitment: Option<pedersen::Commitment<C>>,
+
+    // witnesses; `values` is padded with zeros to one value per generator
+    pub values: Option<Vec<C::ScalarField>>,
+    pub r: Option<pedersen::Randomness<C>>,
+
+    pub _curve_var: PhantomData<GG>,
+}
+
+impl<C, GG> PedersenOpeningCircuit<C, GG>
+where
+    C: CurveGroup,
+    GG: InputLayout<C>,
+{
+    /// The public inputs for the statement `commitment`, in the order `generate_constraints` allocates them.
+    pub fn public_inputs(commitment: &pedersen::Commitment<C>) -> Vec<C::BaseField> {
+        GG::input_elements(commitment)
+    }
+}
+
+impl<C, GG> ConstraintSynthesizer<C::BaseField> for PedersenOpeningCircuit<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+    C::BaseField: PrimeField,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<C::BaseField>) -> Result<(), SynthesisError> {
+        let len = self.parameters.generators.len();
+
+        // constants
+        let parameters = pedersen::constraints::ParametersVar::new_constant(cs.clone(), self.parameters)?;
+
+        // statements
+        let commitment = pedersen::constraints::CommitmentVar::new_input(cs.clone(), || self.commitment.ok_or(SynthesisError::AssignmentMissing))?;
+
+        // witness; the vector length comes from the parameters so the circuit shape does not depend on it
+        let values = pedersen::constraints::InputVar::new_variable_with_len(cs.clone(), len, || self.values.ok_or(SynthesisError::AssignmentMissing), AllocationMode::Witness)?;
+        let r = pedersen::constraints::RandomnessVar::new_witness(cs.clone(), || self.r.as_ref().ok_or(SynthesisError::AssignmentMissing))?;
+
+        let result_var = pedersen::constraints::PedersenCommGadget::<C, GG>::commit(&parameters, &values, &r)?;
+
+        result_var.enforce_equal(&commitment)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use ark_bn254::Bn254;
+    use ark_crypto_primitives::snark::{CircuitSpecificSetupSNARK, SNARK};
+    use ark_groth16::Groth16;
+    use ark_r1cs_std::fields::fp::FpVar;
+    use ark_r1cs_std::groups::curves::short_weierstrass::ProjectiveVar;
+    use ark_relations::r1cs::ConstraintSynthesizer;
+    use ark_std::{UniformRand, test_rng};
+    use rand::{SeedableRng, RngCore};
+
+    use crate::circuits::pedersen_opening::PedersenOpeningCircuit;
+    use crate::gadgets::commitments::pedersen::{Pedersen, Randomness};
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+    type GG = ark_ed_on_bn254::constraints::EdwardsVar;
+    type Fr = ark_ed_on_bn254::Fr;
+
+    #[test]
+    fn test_pedersen_opening_circuit() {
+        let rng = &mut test_rng();
+
+        let parameters = Pedersen::<C>::setup(3);
+        let values: Vec<Fr> = (0..2).map(|_| Fr::rand(rng)).collect();
+        let r = Randomness::rand(rng);
+        let commitment = Pedersen::<C>::commit(&parameters, &values, &r).unwrap();
+
+        let test_circuit: PedersenOpeningCircuit<C, GG> = PedersenOpeningCircuit {
+            parameters: parameters.clone(),
+            commitment: Some(commitment),
+            values: Some(values.clone()),
+            r: Some(r.clone()),
+            _curve_var: std::marker::PhantomData,
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        test_circuit.clone().generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(
+            PedersenOpeningCircuit::<C, GG>::public_inputs(&commitment),
+            cs.borrow().unwrap().instance_assignment[1..]
+        );
+
+        // a different vector does not open the commitment
+        let mut other = values.clone();
+        other[0] += Fr::from(1u64);
+        let wrong_circuit = PedersenOpeningCircuit {
+            values: Some(other),
+            ..test_circuit.clone()
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        wrong_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        let too_long = PedersenOpeningCircuit {
+            values: Some(vec![Fr::from(1u64); 4]),
+            ..test_circuit
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        assert!(too_long.generate_constraints(cs).is_err());
+    }
+
+    #[test]
+    fn test_public_inputs_short_weierstrass() {
+        type C = ark_bn254::G1Projective;
+        type GG = ProjectiveVar<ark_bn254::g1::Config, FpVar<ark_bn254::Fq>>;
+
+        let rng = &mut test_rng();
+
+        let parameters = Pedersen::<C>::setup(2);
+        let values = vec![ark_bn254::Fr::rand(rng), ark_bn254::Fr::rand(rng)];
+        let r = Randomness::rand(rng);
+        let commitment = Pedersen::<C>::commit(&parameters, &values, &r).unwrap();
+
+        let test_circuit: PedersenOpeningCircuit<C, GG> = PedersenOpeningCircuit {
+            parameters,
+            commitment: Some(commitment),
+            values: Some(values),
+            r: Some(r),
+            _curve_var: std::marker::PhantomData,
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        test_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(
+            PedersenOpeningCircuit::<C, GG>::public_inputs(&commitment),
+            cs.borrow().unwrap().instance_assignment[1..]
+        );
+    }
+
+    #[test]
+    fn test_groth16_pedersen_opening() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameters = Pedersen::<C>::setup(2);
+        let values = vec![Fr::rand(rng), Fr::rand(rng)];
+        let r = Randomness::rand(rng);
+        let commitment = Pedersen::<C>::commit(&parameters, &values, &r).unwrap();
+
+        // the setup only needs the parameters
+        let setup_circuit: PedersenOpeningCircuit<C, GG> = PedersenOpeningCircuit {
+            parameters: parameters.clone(),
+            commitment: None,
+            values: None,
+            r: None,
+            _curve_var: std::marker::PhantomData,
+        };
+        let (pk, vk) = Groth16::<Bn254>::setup(setup_circuit, rng).unwrap();
+
+        let pvk = Groth16::<Bn254>::process_vk(&vk).unwrap();
+
+        let test_circuit: PedersenOpeningCircuit<C, GG> = PedersenOpeningCircuit {
+            parameters: parameters.clone(),
+            commitment: Some(commitment),
+            values: Some(values),
+            r: Some(r),
+            _curve_var: std::marker::PhantomData,
+        };
+        let proof = Groth16::<Bn254>::prove(&pk, test_circuit, rng).unwrap();
+
+        let verify_inputs = PedersenOpeningCircuit::<C, GG>::public_inputs(&commitment);
+        assert!(
+            Groth16::<Bn254>::verify_with_processed_vk(&pvk, &verify_inputs, &proof).unwrap(),
+        );
+
+        let other = Pedersen::<C>::commit(&parameters, &[Fr::from(1u64)], &Randomness::rand(rng)).unwrap();
+        let other_inputs = PedersenOpeningCircuit::<C, GG>::public_inputs(&other);
+        assert!(
+            !Groth16::<Bn254>::verify_with_processed_vk(&pvk, &other_inputs, &proof).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::marker::PhantomData;
+
+use ark_ec::CurveGroup;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::AllocVar;
+use ark_r1cs_std::prelude::Boolean;
+use ark_r1cs_std::prelude::CurveVar;
+use ark_r1cs_std::prelude::EqGadget;
+use ark_r1cs_std::ToConstraintFieldGadget;
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_relations::r1cs::ConstraintSystemRef;
+use ark_relations::r1cs::SynthesisError;
+
+use crate::gadgets::curves::{FixedBaseMulGadget, InputLayout};
+use crate::gadgets::hashes::poseidon::PoseidonField;
+use crate::gadgets::signatures::schnorr;
+
+// s*G = R + Poseidon(R, pk, message)*pk, with the signature (R, s) kept private
+#[derive(Clone)]
+pub struct SchnorrVerificationCircuit<C: CurveGroup, GG: CurveVar<C, C::BaseField>> {
+    // Constants
+    pub parameters: schnorr::Parameters<C>,
+
+    // statements
+    pub pk: Option<schnorr::PublicKey<C>>,
+    pub message: Option<schnorr::Message<C>>,
+
+    // witnesses
+    pub signature: Option<schnorr::Signature<C>>,
+
+    pub _curve_var: PhantomData<GG>,
+}
+
+impl<C, GG> SchnorrVerificationCircuit<C, GG>
+where
+    C: CurveGroup,
+    GG: InputLayout<C> + ToConstraintFieldGadget<C::BaseField>,
+    C::BaseField: PoseidonField,
+{
+    /// The public inputs for the statements `pk` and `message`, in the order `generate_constraints` allocates them.
+    pub fn public_inputs(pk: &schnorr::PublicKey<C>, message: &schnorr::Message<C>) -> Vec<C::BaseField> {
+        let mut inputs = GG::input_elements(pk);
+        inputs.push(*message);
+        inputs
+    }
+}
+
+impl<C, GG> ConstraintSynthesizer<C::BaseField> for SchnorrVerificationCircuit<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C> + ToConstraintFieldGadget<C::BaseField>,
+    C::BaseField: PoseidonField,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<C::BaseField>) -> Result<(), SynthesisError> {
+        // constants
+        let parameters = schnorr::constraints::ParametersVar::new_constant(cs.clone(), self.parameters)?;
+
+        // statements
+        let pk = schnorr::constraints::PublicKeyVar::new_input(cs.clone(), || self.pk.ok_or(SynthesisError::AssignmentMissing))?;
+        let message = FpVar::new_input(cs.clone(), || self.message.ok_or(SynthesisError::AssignmentMissing))?;
+
+        // witness
+        let signature = schnorr::constraints::SignatureVar::new_witness(cs.clone(), || self.signature.ok_or(SynthesisError::AssignmentMissing))?;
+
+        let valid = schnorr::constraints::SchnorrVerifyGadget::<C, GG>::verify(&parameters, &pk, &message, &signature)?;
+
+        valid.enforce_equal(&Boolean::TRUE)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use ark_bn254::Bn254;
+    use ark_crypto_primitives::snark::{CircuitSpecificSetupSNARK, SNARK};
+    use ark_groth16::Groth16;
+    use ark_relations::r1cs::ConstraintSynthesizer;
+    use ark_std::{UniformRand, test_rng};
+    use rand::{SeedableRng, RngCore};
+
+    use crate::circuits::schnorr_verification::SchnorrVerificationCircuit;
+    use crate::gadgets::signatures::schnorr::{Schnorr, DEFAULT_DOMAIN};
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+    type GG = ark_ed_on_bn254::constraints::EdwardsVar;
+    type Fq = ark_ed_on_bn254::Fq;
+
+    #[test]
+    fn test_schnorr_verification_circuit() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameters = Schnorr::<C>::setup_transparent(DEFAULT_DOMAIN);
+        let (pk, sk) = Schnorr::<C>::keygen(&parameters, rng);
+        let message = Fq::rand(rng);
+        let signature = Schnorr::<C>::sign(&parameters, &sk, &message, rng).unwrap();
+
+        let test_circuit: SchnorrVerificationCircuit<C, GG> = SchnorrVerificationCircuit {
+            parameters: parameters.clone(),
+            pk: Some(pk),
+            message: Some(message),
+            signature: Some(signature),
+            _curve_var: std::marker::PhantomData,
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        test_circuit.clone().generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(
+            SchnorrVerificationCircuit::<C, GG>::public_inputs(&pk, &message),
+            cs.borrow().unwrap().instance_assignment[1..]
+        );
+
+        // the signature does not cover another message
+        let wrong_circuit = SchnorrVerificationCircuit {
+            message: Some(message + Fq::from(1u64)),
+            ..test_circuit
+        };
+        let cs = ark_relations::r1cs::ConstraintSystem::new_ref();
+        wrong_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_groth16_schnorr_verification() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameters = Schnorr::<C>::setup_transparent(DEFAULT_DOMAIN);
+        let (pk, sk) = Schnorr::<C>::keygen(&parameters, rng);
+        let message = Fq::rand(rng);
+        let signature = Schnorr::<C>::sign(&parameters, &sk, &message, rng).unwrap();
+
+        // the setup only needs the parameters
+        let setup_circuit: SchnorrVerificationCircuit<C, GG> = SchnorrVerificationCircuit {
+            parameters: parameters.clone(),
+            pk: None,
+            message: None,
+            signature: None,
+            _curve_var: std::marker::PhantomData,
+        };
+        let (proving_key, vk) = Groth16::<Bn254>::setup(setup_circuit, rng).unwrap();
+
+        let pvk = Groth16::<Bn254>::process_vk(&vk).unwrap();
+
+        let test_circuit: SchnorrVerificationCircuit<C, GG> = SchnorrVerificationCircuit {
+            parameters: parameters.clone(),
+            pk: Some(pk),
+            message: Some(message),
+            signature: Some(signature),
+            _curve_var: std::marker::PhantomData,
+        };
+        let proof = Groth16::<Bn254>::prove(&proving_key, test_circuit, rng).unwrap();
+
+        let verify_inputs = SchnorrVerificationCircuit::<C, GG>::public_inputs(&pk, &message);
+        assert!(
+            Groth16::<Bn254>::verify_with_processed_vk(&pvk, &verify_inputs, &proof).unwrap(),
+        );
+
+        let (other_pk, _) = Schnorr::<C>::keygen(&parameters, rng);
+        let other_inputs = SchnorrVerificationCircuit::<C, GG>::public_inputs(&other_pk, &message);
+        assert!(
+            !Groth16::<Bn254>::verify_with_processed_vk(&pvk, &other_inputs, &proof).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::{borrow::Borrow, marker::PhantomData};
+
+use ark_ec::CurveGroup;
+use ark_ff::{Field, PrimeField};
+use ark_r1cs_std::prelude::{AllocVar, AllocationMode, Boolean, CurveVar, EqGadget};
+use ark_r1cs_std::R1CSVar;
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
+
+use crate::gadgets::curves::{fixed_base_scalar_mul_le, window_tables, FixedBaseMulGadget, FixedBaseTable};
+use crate::gadgets::public_encryptions::elgamal::constraints::alloc_scalar_bits_le;
+
+pub use crate::gadgets::public_encryptions::elgamal::constraints::RandomnessVar;
+
+use super::*;
+
+#[derive(Clone)]
+pub struct ParametersVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    pub blinding: GG,
+    pub generators: Vec<GG>,
+    /// Window tables of `H` and each `G_j`, precomputed when the parameters are allocated as a
+    /// constant.
+    pub blinding_table: Option<FixedBaseTable<C>>,
+    pub generator_tables: Option<Vec<FixedBaseTable<C>>>,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> AllocVar<Parameters<C>, C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn new_variable<T: Borrow<Parameters<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        // the number of generators is part of the circuit shape, so the value is needed up front
+        let prep = f()?.borrow().clone();
+        let blinding = GG::new_variable(cs.clone(), || Ok(prep.blinding), mode)?;
+        let generators = prep
+            .generators
+            .iter()
+            .map(|g| GG::new_variable(cs.clone(), || Ok(*g), mode))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // constant generators let scalar multiplications use precomputed lookup tables
+        let (blinding_table, generator_tables) = match mode {
+            AllocationMode::Constant => (
+                Some(window_tables::<C>(&prep.blinding)),
+                Some(prep.generators.iter().map(window_tables::<C>).collect()),
+            ),
+            _ => (None, None),
+        };
+
+        Ok(Self {
+            blinding,
+            generators,
+            blinding_table,
+            generator_tables,
+            _curve: PhantomData,
+        })
+    }
+}
+
+/// Little-endian bits of each committed scalar.
+#[derive(Clone)]
+pub struct InputVar<F: Field>(pub Vec<Vec<Boolean<F>>>);
+
+impl<S, F> AllocVar<Vec<S>, F> for InputVar<F>
+where
+    S: PrimeField,
+    F: PrimeField,
+{
+    fn new_variable<T: Borrow<Vec<S>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        // the number of values is part of the circuit shape, so the value is needed up front;
+        // circuits that know it use `new_variable_with_len`
+        let prep = f()?.borrow().clone();
+        Self::new_variable_with_len(cs, prep.len(), || Ok(prep), mode)
+    }
+}
+
+impl<F: PrimeField> InputVar<F> {
+    /// Allocates `len` scalars, padding shorter values with zeros as `Pedersen::commit` does. A
+    /// missing assignment is passed on to every scalar, so a setup without values still succeeds.
+    pub fn new_variable_with_len<S: PrimeField, T: Borrow<Vec<S>>>(
+        cs: impl Into<Namespace<F>>,
+        len: usize,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let prep = f().map(|v| v.borrow().clone());
+        if prep.as_ref().is_ok_and(|v| v.len() > len) {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        (0..len)
+            .map(|i| {
+                let value = prep.as_ref().map(|v| v.get(i).copied().unwrap_or_else(S::zero)).map_err(|e| *e);
+                alloc_scalar_bits_le(cs.clone(), value, mode)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+#[derive(Clone)]
+pub struct CommitmentVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    pub commitment: GG,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> AllocVar<Commitment<C>, C::BaseField> for CommitmentVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn new_variable<T: Borrow<Commitment<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let commitment = GG::new_variable(cs, f, mode)?;
+        Ok(Self {
+            commitment,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> R1CSVar<C::BaseField> for CommitmentVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    type Value = Commitment<C>;
+
+    fn cs(&self) -> ConstraintSystemRef<C::BaseField> {
+        self.commitment.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        self.commitment.value().map(|c| c.into_affine())
+    }
+}
+
+impl<C, GG> EqGadget<C::BaseField> for CommitmentVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        self.commitment.is_eq(&other.commitment)
+    }
+}
+
+#[derive(Clone)]
+pub struct PedersenCommGadget<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    #[doc(hidden)]
+    _curve: PhantomData<*const C>,
+    _group_var: PhantomData<*const GG>,
+}
+
+impl<C, GG> PedersenCommGadget<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+{
+    /// Computes `r*H + sum_i m_i*G_i`, as `Pedersen::commit` does natively.
+    pub fn commit(
+        parameters: &ParametersVar<C, GG>,
+        input: &InputVar<C::BaseField>,
+        randomness: &RandomnessVar<C::BaseField>,
+    ) -> Result<CommitmentVar<C, GG>, SynthesisError> {
+        if input.0.len() > parameters.generators.len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        // compute r*H
+        let mut commitment =
+            fixed_base_scalar_mul_le(&parameters.blinding, parameters.blinding_table.as_ref(), &randomness.0)?;
+
+        // add m_i*G_i
+        for (i, bits) in input.0.iter().enumerate() {
+            let table = parameters.generator_tables.as_ref().map(|t| &t[i]);
+            commitment += fixed_base_scalar_mul_le(&parameters.generators[i], table, bits)?;
+        }
+
+        Ok(CommitmentVar {
+            commitment,
+            _curve: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ed_on_bn254::{constraints::EdwardsVar, EdwardsProjective, Fq, Fr};
+    use ark_r1cs_std::prelude::{AllocVar, AllocationMode, EqGadget};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::{ConstraintSystem, SynthesisError, SynthesisMode};
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::gadgets::commitments::pedersen::constraints::{
+        CommitmentVar, InputVar, ParametersVar, PedersenCommGadget, RandomnessVar,
+    };
+    use crate::gadgets::commitments::pedersen::{Pedersen, Randomness};
+
+    type MyComm = Pedersen<EdwardsProjective>;
+    type MyGadget = PedersenCommGadget<EdwardsProjective, EdwardsVar>;
+
+    #[test]
+    fn test_pedersen_gadget() {
+        let rng = &mut test_rng();
+
+        let parameters = MyComm::setup(3);
+        let values: Vec<Fr> = (0..3).map(|_| Fr::rand(rng)).collect();
+        let randomness = Randomness::rand(rng);
+        let primitive_result = MyComm::commit(&parameters, &values, &randomness).unwrap();
+
+        // constant parameters use lookup tables, witness parameters the generic multiplication
+        for constant in [true, false] {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let parameters_var = if constant {
+                ParametersVar::new_constant(cs.clone(), &parameters).unwrap()
+            } else {
+                ParametersVar::new_witness(cs.clone(), || Ok(&parameters)).unwrap()
+            };
+            let input_var = InputVar::new_witness(cs.clone(), || Ok(&values)).unwrap();
+            let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+            let expected_var = CommitmentVar::new_input(cs.clone(), || Ok(primitive_result)).unwrap();
+
+            let result_var = MyGadget::commit(&parameters_var, &input_var, &randomness_var).unwrap();
+            assert_eq!(primitive_result, result_var.value().unwrap());
+            result_var.enforce_equal(&expected_var).unwrap();
+            assert!(cs.is_satisfied().unwrap());
+        }
+
+        // too many values for the parameters
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let parameters_var = ParametersVar::<EdwardsProjective, EdwardsVar>::new_constant(cs.clone(), &parameters).unwrap();
+        let input_var = InputVar::new_witness(cs.clone(), || Ok(vec![Fr::from(1u64); 4])).unwrap();
+        let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+        assert!(MyGadget::commit(&parameters_var, &input_var, &randomness_var).is_err());
+
+        // a setup without values still allocates every scalar; a longer value is rejected
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        cs.set_mode(SynthesisMode::Setup);
+        let missing = || Err::<Vec<Fr>, _>(SynthesisError::AssignmentMissing);
+        let input_var = InputVar::new_variable_with_len(cs, 3, missing, AllocationMode::Witness).unwrap();
+        assert_eq!(3, input_var.0.len());
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        assert!(InputVar::new_variable_with_len(cs, 2, || Ok(&values), AllocationMode::Witness).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use std::marker::PhantomData;
+use std::ops::Mul;
+
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use crate::gadgets::public_encryptions::elgamal::hash_to_curve;
+
+pub use crate::gadgets::public_encryptions::elgamal::Randomness;
+
+pub mod constraints;
+
+/// Domain separation string for the default parameters.
+pub const DEFAULT_DOMAIN: &[u8] = b"arkworks-study/pedersen/generators/v1";
+
+/// Pedersen vector commitment `r*H + sum_i m_i*G_i` to scalars `m_i`.
+///
+/// Perfectly hiding, and binding as long as nobody knows a discrete log relation between the
+/// generators, which is why they are derived by hashing to the curve.
+pub struct Pedersen<C: CurveGroup> {
+    _group: PhantomData<C>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Parameters<C: CurveGroup> {
+    /// `H`, the blinding generator.
+    pub blinding: C::Affine,
+    /// `G_i`, one per committed scalar.
+    pub generators: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> Parameters<C> {
+    /// Parameters for vectors of up to `len` scalars; the generators are
+    /// `hash_to_curve(domain, "blinding")` and `hash_to_curve(domain, "generator" || i)`.
+    pub fn derive(domain: &[u8], len: usize) -> Self {
+        let blinding = hash_to_curve::<C>(domain, b"blinding");
+        let generators = (0..len as u64)
+            .map(|i| hash_to_curve::<C>(domain, &[&b"generator"[..], &i.to_le_bytes()].concat()))
+            .collect();
+        Self { blinding, generators }
+    }
+
+    /// Checks that these parameters were produced by `Parameters::derive(domain, len)`.
+    pub fn verify(&self, domain: &[u8]) -> bool {
+        *self == Self::derive(domain, self.generators.len())
+    }
+}
+
+pub type Commitment<C> = <C as CurveGroup>::Affine;
+
+impl<C: CurveGroup> Pedersen<C> {
+    pub fn setup(len: usize) -> Parameters<C> {
+        Parameters::derive(DEFAULT_DOMAIN, len)
+    }
+
+    /// Commits to `values`, which may be shorter than the parameters; missing entries count as zero.
+    pub fn commit(
+        pp: &Parameters<C>,
+        values: &[C::ScalarField],
+        r: &Randomness<C>,
+    ) -> Result<Commitment<C>, ark_crypto_primitives::Error> {
+        if values.len() > pp.generators.len() {
+            return Err("more values than the parameters have generators".into());
+        }
+
+        let commitment = C::msm_unchecked(&pp.generators[..values.len()], values) + pp.blinding.mul(r.0);
+        Ok(commitment.into_affine())
+    }
+
+    /// Checks that `(values, r)` opens `commitment`.
+    pub fn verify(pp: &Parameters<C>, commitment: &Commitment<C>, values: &[C::ScalarField], r: &Randomness<C>) -> bool {
+        Self::commit(pp, values, r).is_ok_and(|c| c == *commitment)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::CurveGroup;
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{Parameters, Pedersen, Randomness, DEFAULT_DOMAIN};
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+    type Fr = ark_ed_on_bn254::Fr;
+
+    #[test]
+    fn test_pedersen_commitment() {
+        let rng = &mut test_rng();
+
+        let parameters = Pedersen::<C>::setup(4);
+        assert!(parameters.verify(DEFAULT_DOMAIN));
+        assert_eq!(parameters, Parameters::derive(DEFAULT_DOMAIN, 4));
+        assert!(!Parameters::<C>::derive(b"other", 4).verify(DEFAULT_DOMAIN));
+        assert!(parameters.generators.iter().all(|g| g.is_in_correct_subgroup_assuming_on_curve()));
+
+        let values: Vec<Fr> = (0..4).map(|_| Fr::rand(rng)).collect();
+        let r = Randomness::rand(rng);
+        let commitment = Pedersen::<C>::commit(&parameters, &values, &r).unwrap();
+        assert!(Pedersen::<C>::verify(&parameters, &commitment, &values, &r));
+
+        let mut other = values.clone();
+        other[3] += Fr::from(1u64);
+        assert!(!Pedersen::<C>::verify(&parameters, &commitment, &other, &r));
+        assert!(!Pedersen::<C>::verify(&parameters, &commitment, &values, &Randomness::rand(rng)));
+
+        // a single value is a prefix of the vector, and trailing zeros do not change the commitment
+        let single = Pedersen::<C>::commit(&parameters, &values[..1], &r).unwrap();
+        let padded = [values[0], Fr::from(0u64)];
+        assert!(Pedersen::<C>::verify(&parameters, &single, &padded, &r));
+        assert_eq!((parameters.generators[0] * values[0] + parameters.blinding * r.0).into_affine(), single);
+
+        assert!(Pedersen::<C>::commit(&parameters, &[Fr::from(1u64); 5], &r).is_err());
+    }
+
+    #[test]
+    fn test_pedersen_homomorphism() {
+        let rng = &mut test_rng();
+
+        let parameters = Pedersen::<C>::setup(2);
+        let (a, b) = ([Fr::rand(rng), Fr::rand(rng)], [Fr::rand(rng), Fr::rand(rng)]);
+        let (ra, rb) = (Randomness::rand(rng), Randomness::rand(rng));
+
+        let ca = Pedersen::<C>::commit(&parameters, &a, &ra).unwrap();
+        let cb = Pedersen::<C>::commit(&parameters, &b, &rb).unwrap();
+        let sum = [a[0] + b[0], a[1] + b[1]];
+        let r_sum = Randomness(ra.0 + rb.0);
+        assert!(Pedersen::<C>::verify(&parameters, &(ca + cb).into_affine(), &sum, &r_sum));
+    }
+}
@@ -0,0 +1,247 @@
+use ark_ec::short_weierstrass::{Projective as SWProjective, SWCurveConfig};
+use ark_ec::twisted_edwards::{Projective as TEProjective, TECurveConfig};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, PrimeField, Zero};
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::groups::curves::short_weierstrass::ProjectiveVar;
+use ark_r1cs_std::groups::curves::twisted_edwards::AffineVar;
+use ark_r1cs_std::prelude::{Boolean, CurveVar, FieldVar};
+use ark_relations::r1cs::SynthesisError;
+
+/// Number of scalar bits looked up at once by `FixedBaseMulGadget::windowed_scalar_mul_le`.
+pub const WINDOW: usize = 3;
+
+/// `k * 2^(WINDOW*j) * base` at `[j][k]`, for every window `j` of a scalar and every `k` below
+/// `2^WINDOW`.
+pub type FixedBaseTable<C> = Vec<Vec<<C as CurveGroup>::Affine>>;
+
+/// The lookup tables of `fixed_base_scalar_mul_le` for `base`.
+pub fn window_tables<C: CurveGroup>(base: &C::Affine) -> FixedBaseTable<C> {
+    let windows = (C::ScalarField::MODULUS_BIT_SIZE as usize).div_ceil(WINDOW);
+    let mut window_base = base.into_group();
+    (0..windows)
+        .map(|_| {
+            let mut entries = vec![C::zero(); 1 << WINDOW];
+            for k in 1..entries.len() {
+                entries[k] = entries[k - 1] + window_base;
+            }
+            for _ in 0..WINDOW {
+                window_base.double_in_place();
+            }
+            C::normalize_batch(&entries)
+        })
+        .collect()
+}
+
+/// Curve gadgets that can multiply a constant base by a scalar through its `window_tables`.
+pub trait FixedBaseMulGadget<C: CurveGroup>: CurveVar<C, C::BaseField> {
+    /// Computes `bits * base`, where `table` covers every bit of `bits`.
+    fn windowed_scalar_mul_le(
+        table: &FixedBaseTable<C>,
+        bits: &[Boolean<C::BaseField>],
+    ) -> Result<Self, SynthesisError>;
+}
+
+/// Looks up `values[k]` for the window whose bits spell `k`, given every product of those bits
+/// from `window_monomials`. Over `{0, 1}` the lookup is the multilinear polynomial through
+/// `values`, so it is a linear combination of the products and costs no constraints.
+fn window_lookup<F: PrimeField>(monomials: &[Boolean<F>], mut values: Vec<F>) -> FpVar<F> {
+    // Möbius transform from the values at each corner to the coefficient of each product
+    for i in 0..monomials.len().trailing_zeros() {
+        for s in 0..values.len() {
+            if s >> i & 1 == 1 {
+                values[s] = values[s] - values[s ^ (1 << i)];
+            }
+        }
+    }
+    monomials
+        .iter()
+        .zip(values)
+        .fold(FpVar::zero(), |acc, (monomial, c)| acc + FpVar::from(monomial.clone()) * c)
+}
+
+/// The product of the bits in every subset `s` of `bits`, indexed by `s` as a bit mask. Costs one
+/// constraint per subset of two or more bits.
+fn window_monomials<F: PrimeField>(bits: &[Boolean<F>]) -> Result<Vec<Boolean<F>>, SynthesisError> {
+    let mut monomials = vec![Boolean::TRUE; 1 << bits.len()];
+    for s in 1..monomials.len() {
+        let low = s.trailing_zeros() as usize;
+        let rest = s & (s - 1);
+        monomials[s] = if rest == 0 {
+            bits[low].clone()
+        } else {
+            monomials[rest].and(&bits[low])?
+        };
+    }
+    Ok(monomials)
+}
+
+/// Twisted Edwards points are looked up a window at a time and accumulated with complete
+/// additions, so a scalar costs one addition per `WINDOW` bits and no doublings.
+impl<P> FixedBaseMulGadget<TEProjective<P>> for AffineVar<P, FpVar<P::BaseField>>
+where
+    P: TECurveConfig,
+    P::BaseField: PrimeField,
+{
+    fn windowed_scalar_mul_le(
+        table: &FixedBaseTable<TEProjective<P>>,
+        bits: &[Boolean<P::BaseField>],
+    ) -> Result<Self, SynthesisError> {
+        let mut result: Option<Self> = None;
+        for (entries, bits) in table.iter().zip(bits.chunks(WINDOW)) {
+            let monomials = window_monomials(bits)?;
+            let entries = &entries[..monomials.len()];
+            let x = window_lookup(&monomials, entries.iter().map(|p| p.x).collect());
+            let y = window_lookup(&monomials, entries.iter().map(|p| p.y).collect());
+
+            let point = Self::new(x, y);
+            result = Some(match result {
+                Some(acc) => acc + point,
+                None => point,
+            });
+        }
+        Ok(result.unwrap_or_else(Self::zero))
+    }
+}
+
+/// `ProjectiveVar` adds a constant point for fewer constraints than a window lookup costs, so
+/// short Weierstrass curves take the powers of two from the tables and add them bit by bit.
+impl<P> FixedBaseMulGadget<SWProjective<P>> for ProjectiveVar<P, FpVar<P::BaseField>>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+{
+    fn windowed_scalar_mul_le(
+        table: &FixedBaseTable<SWProjective<P>>,
+        bits: &[Boolean<P::BaseField>],
+    ) -> Result<Self, SynthesisError> {
+        let multiples = table
+            .iter()
+            .flat_map(|entries| (0..WINDOW).map(move |i| entries[1 << i].into_group()))
+            .collect::<Vec<_>>();
+        let mut result = Self::zero();
+        result.precomputed_base_scalar_mul_le(bits.iter().zip(&multiples))?;
+        Ok(result)
+    }
+}
+
+/// Computes `bits * base`, through `table` from `window_tables` when available. A table shorter
+/// than `bits` would silently drop the high bits, so it falls back to `scalar_mul_le` instead.
+pub fn fixed_base_scalar_mul_le<C, GG>(
+    base: &GG,
+    table: Option<&FixedBaseTable<C>>,
+    bits: &[Boolean<C::BaseField>],
+) -> Result<GG, SynthesisError>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+{
+    match table {
+        Some(table) if table.len() * WINDOW >= bits.len() => GG::windowed_scalar_mul_le(table, bits),
+        _ => base.clone().scalar_mul_le(bits.iter()),
+    }
+}
+
+/// The field elements a curve gadget allocates for a point passed to `new_input`, in order.
+///
+/// Circuits build the public inputs for keys, ciphertexts and commitments from this rather than
+/// `ToConstraintField`. The two agree for twisted Edwards curves, but short Weierstrass points
+/// give `[x, y, infinity]` there, while `ProjectiveVar` allocates `[x, y, z]`.
+pub trait InputLayout<C: CurveGroup>: CurveVar<C, C::BaseField> {
+    fn input_elements(point: &C::Affine) -> Vec<C::BaseField>;
+}
+
+impl<P> InputLayout<TEProjective<P>> for AffineVar<P, FpVar<P::BaseField>>
+where
+    P: TECurveConfig,
+    P::BaseField: PrimeField,
+{
+    fn input_elements(point: &<TEProjective<P> as CurveGroup>::Affine) -> Vec<P::BaseField> {
+        vec![point.x, point.y]
+    }
+}
+
+impl<P> InputLayout<SWProjective<P>> for ProjectiveVar<P, FpVar<P::BaseField>>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+{
+    fn input_elements(point: &<SWProjective<P> as CurveGroup>::Affine) -> Vec<P::BaseField> {
+        match point.xy() {
+            Some((x, y)) => vec![*x, *y, P::BaseField::one()],
+            None => vec![P::BaseField::zero(), P::BaseField::one(), P::BaseField::zero()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::{G1Affine, G1Projective};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_ed_on_bn254::{constraints::EdwardsVar, EdwardsAffine, EdwardsProjective};
+    use ark_r1cs_std::fields::fp::FpVar;
+    use ark_r1cs_std::groups::curves::short_weierstrass::ProjectiveVar;
+    use ark_r1cs_std::prelude::{AllocVar, Boolean};
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{fixed_base_scalar_mul_le, window_tables, FixedBaseMulGadget, InputLayout};
+
+    type G1Var = ProjectiveVar<ark_bn254::g1::Config, FpVar<ark_bn254::Fq>>;
+
+    // allocates `points` as inputs and compares the instance assignment with `input_elements`
+    fn check<C: CurveGroup, GG: InputLayout<C>>(points: &[C::Affine]) {
+        let cs = ConstraintSystem::<C::BaseField>::new_ref();
+        let mut expected = Vec::new();
+        for point in points {
+            GG::new_input(cs.clone(), || Ok(point.into_group())).unwrap();
+            expected.extend(GG::input_elements(point));
+        }
+        assert_eq!(expected, cs.borrow().unwrap().instance_assignment[1..]);
+    }
+
+    #[test]
+    fn test_input_layout() {
+        let rng = &mut test_rng();
+
+        check::<EdwardsProjective, EdwardsVar>(&[EdwardsAffine::rand(rng), EdwardsAffine::zero()]);
+        // short Weierstrass points carry `z`, which `to_field_elements` does not
+        check::<G1Projective, G1Var>(&[G1Affine::rand(rng), G1Affine::zero()]);
+    }
+
+    // `bits * base` through the window tables of a constant base, compared with the native result
+    fn check_fixed_base<C: CurveGroup, GG: FixedBaseMulGadget<C>>(scalar: C::ScalarField, len: usize) {
+        let rng = &mut test_rng();
+
+        let base = C::Affine::rand(rng);
+        let cs = ConstraintSystem::<C::BaseField>::new_ref();
+        let base_var = GG::new_constant(cs.clone(), base.into_group()).unwrap();
+        let bits = scalar.into_bigint().to_bits_le()[..len]
+            .iter()
+            .map(|b| Boolean::new_witness(cs.clone(), || Ok(*b)).unwrap())
+            .collect::<Vec<_>>();
+        let expected = (base * scalar).into_affine();
+
+        let table = window_tables::<C>(&base);
+        let result = fixed_base_scalar_mul_le(&base_var, Some(&table), &bits).unwrap();
+        assert_eq!(expected, result.value().unwrap().into_affine());
+
+        // a table too short for the bits falls back instead of dropping the high bits
+        let short = table[..2].to_vec();
+        let result = fixed_base_scalar_mul_le(&base_var, Some(&short), &bits).unwrap();
+        assert_eq!(expected, result.value().unwrap().into_affine());
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_fixed_base_scalar_mul() {
+        let rng = &mut test_rng();
+
+        // a partial last window, and the full width of the scalar field
+        check_fixed_base::<EdwardsProjective, EdwardsVar>(ark_ed_on_bn254::Fr::from(0xbeefu64), 16);
+        check_fixed_base::<EdwardsProjective, EdwardsVar>(ark_ed_on_bn254::Fr::rand(rng), 251);
+        check_fixed_base::<EdwardsProjective, EdwardsVar>(ark_ed_on_bn254::Fr::from(0u64), 251);
+        check_fixed_base::<G1Projective, G1Var>(ark_bn254::Fr::rand(rng), 254);
+    }
+}
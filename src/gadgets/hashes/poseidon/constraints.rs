@@ -0,0 +1,83 @@
+use ark_crypto_primitives::sponge::constraints::CryptographicSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::PoseidonConfig;
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::R1CSVar;
+use ark_relations::r1cs::SynthesisError;
+
+use super::PoseidonField;
+
+/// In-circuit `hash_with_config`. A length other than `config.rate` is `Unsatisfiable`.
+pub fn hash_with_config<F: PrimeField>(
+    config: &PoseidonConfig<F>,
+    inputs: &[FpVar<F>],
+) -> Result<FpVar<F>, SynthesisError> {
+    if config.rate != inputs.len() {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let mut sponge = PoseidonSpongeVar::new(inputs.cs(), config);
+    sponge.absorb(&inputs)?;
+    // squeezing runs the permutation; circomlib outputs the capacity element rather than the rate
+    sponge.squeeze_field_elements(1)?;
+    Ok(sponge.state[0].clone())
+}
+
+/// In-circuit `hash`, producing the same value for the same inputs.
+pub fn hash<F: PoseidonField>(inputs: &[FpVar<F>]) -> Result<FpVar<F>, SynthesisError> {
+    let config = F::poseidon_config(inputs.len()).map_err(|_| SynthesisError::Unsatisfiable)?;
+    hash_with_config(config, inputs)
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_r1cs_std::fields::fp::FpVar;
+    use ark_r1cs_std::prelude::{AllocVar, EqGadget};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::{ConstraintSystem, SynthesisError};
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::gadgets::hashes::poseidon;
+
+    #[test]
+    fn test_poseidon_gadget() {
+        let rng = &mut test_rng();
+
+        for n in [1, 2, 4, 16] {
+            let inputs = (0..n).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+            let expected = poseidon::hash(&inputs).unwrap();
+
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let input_vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(inputs.clone())).unwrap();
+            let digest_var = FpVar::new_input(cs.clone(), || Ok(expected)).unwrap();
+
+            let result_var = poseidon::constraints::hash(&input_vars).unwrap();
+            assert_eq!(expected, result_var.value().unwrap());
+            result_var.enforce_equal(&digest_var).unwrap();
+            assert!(cs.is_satisfied().unwrap());
+        }
+
+        // a different preimage does not reach the same digest
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let input_vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(vec![Fr::from(1u64), Fr::from(3u64)])).unwrap();
+        let digest_var = FpVar::new_input(cs.clone(), || Ok(poseidon::hash(&[Fr::from(1u64), Fr::from(2u64)]).unwrap())).unwrap();
+        poseidon::constraints::hash(&input_vars).unwrap().enforce_equal(&digest_var).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_poseidon_gadget_unsupported_length() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let input_vars = Vec::<FpVar<Fr>>::new_witness(cs, || Ok(vec![Fr::from(1u64); poseidon::MAX_INPUTS + 1])).unwrap();
+
+        assert!(matches!(poseidon::constraints::hash(&input_vars), Err(SynthesisError::Unsatisfiable)));
+        assert!(matches!(poseidon::constraints::hash::<Fr>(&[]), Err(SynthesisError::Unsatisfiable)));
+        let config = <Fr as poseidon::PoseidonField>::poseidon_config(2).unwrap();
+        assert!(matches!(
+            poseidon::constraints::hash_with_config(config, &input_vars[..1]),
+            Err(SynthesisError::Unsatisfiable)
+        ));
+    }
+}
@@ -0,0 +1,140 @@
+//! Poseidon over the BN254 scalar field, compatible with circomlib's `Poseidon(n)` template.
+//!
+//! `hash` absorbs `n` inputs into a width `n + 1` state behind a zero capacity element, applies
+//! one permutation and outputs the capacity element, exactly like circomlib and its JavaScript
+//! and Solidity ports. The permutation itself is the one of ark-crypto-primitives' sponge.
+
+use std::sync::OnceLock;
+
+use ark_crypto_primitives::Error;
+use ark_crypto_primitives::sponge::poseidon::{find_poseidon_ark_and_mds, PoseidonConfig, PoseidonSponge};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ff::PrimeField;
+
+pub mod constraints;
+
+/// Full rounds of every instance, split evenly before and after the partial rounds.
+pub const FULL_ROUNDS: usize = 8;
+
+/// Partial rounds for `1..=MAX_INPUTS` inputs, i.e. state widths `2..=17`, as in circomlib.
+pub const PARTIAL_ROUNDS: [usize; MAX_INPUTS] = [56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68];
+
+/// Exponent of the S-box.
+pub const ALPHA: u64 = 5;
+
+/// Largest number of inputs `hash` accepts.
+pub const MAX_INPUTS: usize = 16;
+
+/// Fields with vetted Poseidon instances.
+pub trait PoseidonField: PrimeField + Absorb {
+    /// The instance hashing `rate` inputs. Fails unless `1 <= rate <= MAX_INPUTS`.
+    fn poseidon_config(rate: usize) -> Result<&'static PoseidonConfig<Self>, Error>;
+}
+
+/// The instance for `rate` inputs over a ~254-bit field. Round constants and the MDS matrix come
+/// from the Grain LFSR of the Poseidon reference implementation, which also produced circomlib's.
+pub(crate) fn grain_config<F: PrimeField>(rate: usize) -> PoseidonConfig<F> {
+    let partial_rounds = PARTIAL_ROUNDS[rate - 1];
+    let (ark, mds) = find_poseidon_ark_and_mds::<F>(
+        F::MODULUS_BIT_SIZE as u64,
+        rate,
+        FULL_ROUNDS as u64,
+        partial_rounds as u64,
+        0,
+    );
+    PoseidonConfig::new(FULL_ROUNDS, partial_rounds, ALPHA, mds, ark, rate, 1)
+}
+
+/// BN254's scalar field, which is also the base field of `ark_ed_on_bn254` (`ark_ed_on_bn254::Fq`
+/// is the same type), so both get these parameters.
+impl PoseidonField for ark_bn254::Fr {
+    fn poseidon_config(rate: usize) -> Result<&'static PoseidonConfig<Self>, Error> {
+        if !(1..=MAX_INPUTS).contains(&rate) {
+            return Err(format!("unsupported Poseidon rate {}", rate).into());
+        }
+
+        static CONFIGS: [OnceLock<PoseidonConfig<ark_bn254::Fr>>; MAX_INPUTS] = [const { OnceLock::new() }; MAX_INPUTS];
+        Ok(CONFIGS[rate - 1].get_or_init(|| grain_config(rate)))
+    }
+}
+
+/// Hashes `inputs` with an explicit configuration. One call hashes exactly `config.rate` inputs;
+/// any other length is an error.
+pub fn hash_with_config<F: PrimeField + Absorb>(config: &PoseidonConfig<F>, inputs: &[F]) -> Result<F, Error> {
+    if config.rate != inputs.len() {
+        return Err(format!("Poseidon of rate {} cannot hash {} inputs", config.rate, inputs.len()).into());
+    }
+
+    let mut sponge = PoseidonSponge::new(config);
+    sponge.absorb(&inputs);
+    // squeezing runs the permutation; circomlib outputs the capacity element rather than the rate
+    let _ = sponge.squeeze_field_elements::<F>(1);
+    Ok(sponge.state[0])
+}
+
+/// Circomlib-compatible Poseidon hash of `1..=MAX_INPUTS` field elements.
+pub fn hash<F: PoseidonField>(inputs: &[F]) -> Result<F, Error> {
+    hash_with_config(F::poseidon_config(inputs.len())?, inputs)
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use ark_bn254::Fr;
+    use ark_ff::PrimeField;
+
+    use super::{hash, hash_with_config, PoseidonField, MAX_INPUTS};
+
+    fn from_hex(hex: &str) -> Fr {
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+        Fr::from_be_bytes_mod_order(&bytes)
+    }
+
+    // outputs of circomlibjs' `poseidon`
+    #[test]
+    fn test_poseidon_known_answers() {
+        let inputs = |n: u64| (1..=n).map(Fr::from).collect::<Vec<_>>();
+
+        assert_eq!(
+            from_hex("115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"),
+            hash(&inputs(2)).unwrap()
+        );
+        assert_eq!(
+            from_hex("29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133"),
+            hash(&inputs(1)).unwrap()
+        );
+        assert_eq!(
+            from_hex("299c867db6c1fdd79dcefa40e4510b9837e60ebb1ce0663dbaa525df65250465"),
+            hash(&inputs(4)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_poseidon_parameters() {
+        // the first round constant of circomlib's width-3 instance
+        let config = Fr::poseidon_config(2).unwrap();
+        assert_eq!(
+            Fr::from_str("6745197990210204598374042828761989596302876299545964402857411729872131034734").unwrap(),
+            config.ark[0][0]
+        );
+        assert_eq!((8, 57, 5, 3), (config.full_rounds, config.partial_rounds, config.alpha, config.ark[0].len()));
+
+        // the ed_on_bn254 base field is the same field
+        assert_eq!(Fr::from(3u64), ark_ed_on_bn254::Fq::from(3u64));
+        assert!(std::ptr::eq(config, ark_ed_on_bn254::Fq::poseidon_config(2).unwrap()));
+    }
+
+    #[test]
+    fn test_poseidon_unsupported_length() {
+        assert!(hash::<Fr>(&[]).is_err());
+        assert!(hash(&[Fr::from(1u64); MAX_INPUTS + 1]).is_err());
+        assert!(Fr::poseidon_config(0).is_err());
+
+        // a configuration only hashes its own rate
+        assert!(hash_with_config(Fr::poseidon_config(2).unwrap(), &[Fr::from(1u64)]).is_err());
+    }
+}
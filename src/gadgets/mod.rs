@@ -1 +1,5 @@
-pub mod public_encryptions;
\ No newline at end of file
+pub mod commitments;
+pub mod curves;
+pub mod hashes;
+pub mod public_encryptions;
+pub mod signatures;
@@ -0,0 +1,290 @@
+//! Checks that an `AsymmetricEncryptionGadget` agrees with its native `AsymmetricEncryptionScheme`.
+//!
+//! Compiled for this crate's tests and, behind the `test-utils` feature, for downstream ones: the
+//! checks panic with a description of the first mismatch.
+
+use ark_ff::Field;
+use ark_r1cs_std::prelude::{AllocVar, EqGadget};
+use ark_relations::r1cs::{ConstraintSystem, SynthesisError};
+use ark_std::rand::Rng;
+use ark_std::UniformRand;
+
+use super::constraints::AsymmetricEncryptionGadget;
+use super::AsymmetricEncryptionScheme;
+
+/// Size of the constraint system for one encryption, including allocating its inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConformanceReport {
+    pub num_constraints: usize,
+    pub num_instance_variables: usize,
+    pub num_witness_variables: usize,
+}
+
+/// The values a single run feeds to the gadget, and the ciphertext it is compared against.
+struct Case<'a, S: AsymmetricEncryptionScheme> {
+    parameters: &'a S::Parameters,
+    public_key: &'a S::PublicKey,
+    message: &'a S::Plaintext,
+    randomness: &'a S::Randomness,
+    expected: &'a S::Ciphertext,
+}
+
+// derived impls would require `S: Copy`
+impl<S: AsymmetricEncryptionScheme> Clone for Case<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: AsymmetricEncryptionScheme> Copy for Case<'_, S> {}
+
+// encrypts in a fresh constraint system and enforces equality with `case.expected`; parameters
+// are constants, the key and expected ciphertext are public inputs, the rest are witnesses
+fn synthesize<S, G, F>(case: Case<S>) -> Result<(bool, ConformanceReport), SynthesisError>
+where
+    S: AsymmetricEncryptionScheme,
+    G: AsymmetricEncryptionGadget<S, F>,
+    G::OutputVar: EqGadget<F>,
+    F: Field,
+{
+    let cs = ConstraintSystem::<F>::new_ref();
+    let parameters = G::ParametersVar::new_constant(cs.clone(), case.parameters)?;
+    let public_key = G::PublicKeyVar::new_input(cs.clone(), || Ok(case.public_key))?;
+    let expected = G::OutputVar::new_input(cs.clone(), || Ok(case.expected))?;
+    let message = G::PlaintextVar::new_witness(cs.clone(), || Ok(case.message))?;
+    let randomness = G::RandomnessVar::new_witness(cs.clone(), || Ok(case.randomness))?;
+
+    G::encrypt(&parameters, &message, &randomness, &public_key)?.enforce_equal(&expected)?;
+
+    let report = ConformanceReport {
+        num_constraints: cs.num_constraints(),
+        num_instance_variables: cs.num_instance_variables(),
+        num_witness_variables: cs.num_witness_variables(),
+    };
+    Ok((cs.is_satisfied()?, report))
+}
+
+/// Runs `rounds` randomized encryptions natively and in the circuit and returns the size of
+/// the circuit.
+///
+/// Every round checks that the gadget reproduces the native ciphertext, and that the circuit
+/// becomes unsatisfiable when the claimed ciphertext, the public key or the randomness is
+/// replaced by an unrelated one. Plaintexts come from `sample_message`, so schemes with a
+/// restricted message space can stay inside it.
+pub fn check_conformance<S, G, F, R>(
+    rng: &mut R,
+    mut sample_message: impl FnMut(&mut R) -> S::Plaintext,
+    rounds: usize,
+) -> ConformanceReport
+where
+    S: AsymmetricEncryptionScheme,
+    S::Randomness: UniformRand,
+    G: AsymmetricEncryptionGadget<S, F>,
+    G::OutputVar: EqGadget<F>,
+    F: Field,
+    R: Rng,
+{
+    assert!(rounds > 0, "at least one round is needed to measure the circuit");
+
+    let parameters = S::setup(rng).expect("setup failed");
+    let mut report = None;
+
+    for round in 0..rounds {
+        let (public_key, _) = S::keygen(&parameters, rng).expect("keygen failed");
+        let (other_key, _) = S::keygen(&parameters, rng).expect("keygen failed");
+        let message = sample_message(rng);
+        let randomness = S::Randomness::rand(rng);
+        let other_randomness = S::Randomness::rand(rng);
+        let expected = S::encrypt(&parameters, &public_key, &message, &randomness).expect("encryption failed");
+        let other_ciphertext =
+            S::encrypt(&parameters, &public_key, &message, &other_randomness).expect("encryption failed");
+
+        let honest = Case::<S> {
+            parameters: &parameters,
+            public_key: &public_key,
+            message: &message,
+            randomness: &randomness,
+            expected: &expected,
+        };
+        let (satisfied, honest_report) = synthesize::<S, G, F>(honest).expect("synthesis failed");
+        assert!(satisfied, "round {round}: the gadget disagrees with the native encryption");
+
+        // the circuit shape must not depend on the values
+        assert_eq!(
+            *report.get_or_insert(honest_report),
+            honest_report,
+            "round {round}: the constraint system changed shape"
+        );
+
+        let tampered = [
+            ("ciphertext", Case::<S> { expected: &other_ciphertext, ..honest }),
+            ("public key", Case::<S> { public_key: &other_key, ..honest }),
+            ("randomness", Case::<S> { randomness: &other_randomness, ..honest }),
+        ];
+        for (what, case) in tampered {
+            let (satisfied, _) = synthesize::<S, G, F>(case).expect("synthesis failed");
+            assert!(!satisfied, "round {round}: the circuit accepts a tampered {what}");
+        }
+    }
+
+    report.expect("rounds > 0")
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Borrow;
+
+    use ark_ed_on_bn254::{constraints::EdwardsVar, EdwardsProjective, Fq, Fr};
+    use ark_r1cs_std::prelude::{AllocVar, AllocationMode};
+    use ark_relations::r1cs::{Namespace, SynthesisError};
+    use ark_std::rand::Rng;
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{check_conformance, ConformanceReport};
+    use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+    use crate::gadgets::public_encryptions::elgamal::constraints::{
+        ElGamalEncGadget, MultiOutputVar, ParametersVar, PlaintextVar, PublicKeyVar, RandomnessVar,
+    };
+    use crate::gadgets::public_encryptions::elgamal::multi::MultiCiphertext;
+    use crate::gadgets::public_encryptions::elgamal::{self, ElGamal};
+    use crate::gadgets::public_encryptions::lifted_elgamal::{constraints::LiftedElGamalEncGadget, LiftedElGamal};
+    use crate::gadgets::public_encryptions::twisted_elgamal::{constraints::TwistedElGamalEncGadget, TwistedElGamal};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = EdwardsProjective;
+
+    const RECIPIENTS: usize = 3;
+
+    // circuit sizes on ed_on_bn254, as `(constraints, instance variables, witness variables)`;
+    // regression guard: update these numbers deliberately when a gadget changes
+    const ELGAMAL_SIZE: (usize, usize, usize) = (4781, 7, 4637);
+    const MULTI_ELGAMAL_SIZE: (usize, usize, usize) = (11341, 15, 11189);
+    const LIFTED_ELGAMAL_SIZE: (usize, usize, usize) = (4893, 7, 4748);
+    const TWISTED_ELGAMAL_SIZE: (usize, usize, usize) = (6229, 7, 5947);
+
+    fn size(report: ConformanceReport) -> (usize, usize, usize) {
+        (report.num_constraints, report.num_instance_variables, report.num_witness_variables)
+    }
+
+    // `ElGamal::encrypt_multi` to a fixed number of keys, shaped as a scheme for the harness
+    struct MultiElGamal;
+
+    impl AsymmetricEncryptionScheme for MultiElGamal {
+        type Parameters = elgamal::Parameters<C>;
+        type PublicKey = Vec<elgamal::PublicKey<C>>;
+        type SecretKey = Vec<elgamal::SecretKey<C>>;
+        type Randomness = elgamal::Randomness<C>;
+        type Plaintext = elgamal::Plaintext<C>;
+        type Ciphertext = MultiCiphertext<C>;
+
+        fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, ark_crypto_primitives::Error> {
+            ElGamal::<C>::setup(rng)
+        }
+
+        fn keygen<R: Rng>(
+            pp: &Self::Parameters,
+            rng: &mut R,
+        ) -> Result<(Self::PublicKey, Self::SecretKey), ark_crypto_primitives::Error> {
+            let keys = (0..RECIPIENTS)
+                .map(|_| ElGamal::<C>::keygen(pp, rng))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(keys.into_iter().unzip())
+        }
+
+        fn encrypt(
+            pp: &Self::Parameters,
+            pk: &Self::PublicKey,
+            message: &Self::Plaintext,
+            r: &Self::Randomness,
+        ) -> Result<Self::Ciphertext, ark_crypto_primitives::Error> {
+            ElGamal::<C>::encrypt_multi(pp, pk, message, r)
+        }
+
+        fn decrypt(
+            pp: &Self::Parameters,
+            sk: &Self::SecretKey,
+            ciphertext: &Self::Ciphertext,
+        ) -> Result<Self::Plaintext, ark_crypto_primitives::Error> {
+            let ciphertext = ciphertext.for_recipient(0).ok_or("no recipients")?;
+            ElGamal::<C>::decrypt(pp, &sk[0], &ciphertext)
+        }
+    }
+
+    #[derive(Clone)]
+    struct MultiPublicKeyVar(Vec<PublicKeyVar<C, EdwardsVar>>);
+
+    impl AllocVar<Vec<elgamal::PublicKey<C>>, Fq> for MultiPublicKeyVar {
+        fn new_variable<T: Borrow<Vec<elgamal::PublicKey<C>>>>(
+            cs: impl Into<Namespace<Fq>>,
+            f: impl FnOnce() -> Result<T, SynthesisError>,
+            mode: AllocationMode,
+        ) -> Result<Self, SynthesisError> {
+            let ns = cs.into();
+            let cs = ns.cs();
+            let prep = f().map(|pks| pks.borrow().clone());
+            (0..RECIPIENTS)
+                .map(|i| PublicKeyVar::new_variable(cs.clone(), || prep.as_ref().map(|pks| pks[i]).map_err(|e| *e), mode))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Self)
+        }
+    }
+
+    struct MultiElGamalEncGadget;
+
+    impl AsymmetricEncryptionGadget<MultiElGamal, Fq> for MultiElGamalEncGadget {
+        type OutputVar = MultiOutputVar<C, EdwardsVar>;
+        type ParametersVar = ParametersVar<C, EdwardsVar>;
+        type PlaintextVar = PlaintextVar<C, EdwardsVar>;
+        type PublicKeyVar = MultiPublicKeyVar;
+        type RandomnessVar = RandomnessVar<Fq>;
+
+        fn encrypt(
+            parameters: &Self::ParametersVar,
+            message: &Self::PlaintextVar,
+            randomness: &Self::RandomnessVar,
+            public_key: &Self::PublicKeyVar,
+        ) -> Result<Self::OutputVar, SynthesisError> {
+            ElGamalEncGadget::<C, EdwardsVar>::encrypt_multi(parameters, message, randomness, &public_key.0)
+        }
+    }
+
+    #[test]
+    fn test_elgamal_conformance() {
+        let rng = &mut test_rng();
+        let report = check_conformance::<ElGamal<C>, ElGamalEncGadget<C, EdwardsVar>, Fq, _>(
+            rng,
+            |rng| C::rand(rng).into(),
+            2,
+        );
+        assert_eq!(ELGAMAL_SIZE, size(report));
+    }
+
+    #[test]
+    fn test_multi_elgamal_conformance() {
+        let rng = &mut test_rng();
+        let report = check_conformance::<MultiElGamal, MultiElGamalEncGadget, Fq, _>(rng, |rng| C::rand(rng).into(), 2);
+        assert_eq!(MULTI_ELGAMAL_SIZE, size(report));
+    }
+
+    #[test]
+    fn test_lifted_elgamal_conformance() {
+        let rng = &mut test_rng();
+        let report = check_conformance::<LiftedElGamal<C>, LiftedElGamalEncGadget<C, EdwardsVar>, Fq, _>(
+            rng,
+            |rng| Fr::from(u32::rand(rng)),
+            2,
+        );
+        assert_eq!(LIFTED_ELGAMAL_SIZE, size(report));
+    }
+
+    #[test]
+    fn test_twisted_elgamal_conformance() {
+        let rng = &mut test_rng();
+        let report = check_conformance::<TwistedElGamal<C>, TwistedElGamalEncGadget<C, EdwardsVar>, Fq, _>(
+            rng,
+            Fr::rand,
+            2,
+        );
+        assert_eq!(TWISTED_ELGAMAL_SIZE, size(report));
+    }
+}
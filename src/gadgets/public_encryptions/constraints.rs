@@ -19,3 +19,17 @@ pub trait AsymmetricEncryptionGadget<C: AsymmetricEncryptionScheme, ConstraintF:
         public_key: &Self::PublicKeyVar,
     ) -> Result<Self::OutputVar, SynthesisError>;
 }
+
+pub trait AsymmetricDecryptionGadget<C: AsymmetricEncryptionScheme, ConstraintF: Field>:
+    AsymmetricEncryptionGadget<C, ConstraintF>
+{
+    type SecretKeyVar: AllocVar<C::SecretKey, ConstraintF> + Clone;
+
+    /// Decrypts `ciphertext`, enforcing that `secret_key` belongs to `public_key`.
+    fn decrypt(
+        parameters: &Self::ParametersVar,
+        ciphertext: &Self::OutputVar,
+        secret_key: &Self::SecretKeyVar,
+        public_key: &Self::PublicKeyVar,
+    ) -> Result<Self::PlaintextVar, SynthesisError>;
+}
@@ -1,159 +1,805 @@
 use std::{borrow::Borrow, marker::PhantomData};
 
 use ark_ec::CurveGroup;
-use ark_ff::{Field, PrimeField, Zero};
-use ark_r1cs_std::{uint8::UInt8, prelude::{AllocVar, AllocationMode, CurveVar, EqGadget, Boolean}, ToBitsGadget};
-use ark_relations::r1cs::{Namespace, SynthesisError};
-use ark_serialize::CanonicalSerialize;
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::{AllocVar, AllocationMode, CurveVar, EqGadget, Boolean, CondSelectGadget, ToBytesGadget, UInt8};
+use ark_r1cs_std::{R1CSVar, ToConstraintFieldGadget};
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
 
-use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+use crate::gadgets::curves::{fixed_base_scalar_mul_le, window_tables, FixedBaseMulGadget, FixedBaseTable};
+use crate::gadgets::public_encryptions::constraints::{AsymmetricDecryptionGadget, AsymmetricEncryptionGadget};
 
 use super::*;
 
+/// Allocates the little-endian bits of a scalar of `S`, exactly `S::MODULUS_BIT_SIZE` of them.
+/// Non-constant allocations also enforce that the bits encode a canonical element, i.e. a
+/// value smaller than the modulus of `S`.
+pub fn alloc_scalar_bits_le<S, F>(
+    cs: impl Into<Namespace<F>>,
+    value: Result<S, SynthesisError>,
+    mode: AllocationMode,
+) -> Result<Vec<Boolean<F>>, SynthesisError>
+where
+    S: PrimeField,
+    F: PrimeField,
+{
+    let ns = cs.into();
+    let cs = ns.cs();
+    let bits = value.map(|v| v.into_bigint().to_bits_le());
+    let bits = (0..S::MODULUS_BIT_SIZE as usize)
+        .map(|i| Boolean::new_variable(cs.clone(), || bits.as_ref().map(|b| b[i]).map_err(|e| *e), mode))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if mode != AllocationMode::Constant {
+        enforce_canonical_le::<S, F>(&bits)?;
+    }
+    Ok(bits)
+}
+
+/// Enforces that little-endian `bits` encode an integer smaller than the modulus of `S`.
+pub fn enforce_canonical_le<S, F>(bits: &[Boolean<F>]) -> Result<(), SynthesisError>
+where
+    S: PrimeField,
+    F: PrimeField,
+{
+    // bits <= modulus - 1; the modulus is odd, so subtracting one never borrows
+    let mut max = S::MODULUS;
+    max.as_mut()[0] -= 1;
+    Boolean::enforce_smaller_or_equal_than_le(bits, max)?;
+    Ok(())
+}
+
+/// Reads back the scalar encoded by the values of bits allocated with `alloc_scalar_bits_le`.
+/// Bits of the wrong length or above the modulus encode no scalar, so they are reported as a
+/// missing assignment; `Unsatisfiable` is left to the constraint system.
+pub fn scalar_from_bits_le<S: PrimeField>(bits: &[bool]) -> Result<S, SynthesisError> {
+    if bits.len() != S::MODULUS_BIT_SIZE as usize {
+        return Err(SynthesisError::AssignmentMissing);
+    }
+    S::from_bigint(S::BigInt::from_bits_le(bits)).ok_or(SynthesisError::AssignmentMissing)
+}
+
+// packs little-endian bits into bytes, zero-padding the last one
+fn bits_to_bytes_le<F: Field>(bits: &[Boolean<F>]) -> Vec<UInt8<F>> {
+    bits.chunks(8)
+        .map(|chunk| {
+            let mut byte = chunk.to_vec();
+            byte.resize(8, Boolean::FALSE);
+            UInt8::from_bits_le(&byte)
+        })
+        .collect()
+}
+
+// packs little-endian bits into as few field elements as fit without wrapping
+fn bits_to_field_elements_le<F: PrimeField>(bits: &[Boolean<F>]) -> Result<Vec<FpVar<F>>, SynthesisError> {
+    bits.chunks(F::MODULUS_BIT_SIZE as usize - 1)
+        .map(Boolean::le_bits_to_fp_var)
+        .collect()
+}
+
+/// Little-endian bits of the encryption randomness.
+#[derive(Clone)]
+pub struct RandomnessVar<F: Field>(pub Vec<Boolean<F>>);
+
+impl<C, F> AllocVar<Randomness<C>, F> for RandomnessVar<F>
+where
+    C: CurveGroup,
+    F: PrimeField,
+{
+    fn new_variable<T: Borrow<Randomness<C>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        alloc_scalar_bits_le(cs, f().map(|r| r.borrow().0), mode).map(Self)
+    }
+}
+
+impl<F: PrimeField> R1CSVar<F> for RandomnessVar<F> {
+    /// The little-endian bits of the randomness.
+    type Value = Vec<bool>;
+
+    fn cs(&self) -> ConstraintSystemRef<F> {
+        self.0.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        self.0.value()
+    }
+}
+
+impl<F: PrimeField> EqGadget<F> for RandomnessVar<F> {
+    fn is_eq(&self, other: &Self) -> Result<Boolean<F>, SynthesisError> {
+        self.0.is_eq(&other.0)
+    }
+}
+
+impl<F: PrimeField> CondSelectGadget<F> for RandomnessVar<F> {
+    fn conditionally_select(
+        cond: &Boolean<F>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        true_value
+            .0
+            .iter()
+            .zip(&false_value.0)
+            .map(|(t, f)| cond.select(t, f))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+impl<F: PrimeField> ToBytesGadget<F> for RandomnessVar<F> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<F>>, SynthesisError> {
+        Ok(bits_to_bytes_le(&self.0))
+    }
+}
+
+impl<F: PrimeField> ToConstraintFieldGadget<F> for RandomnessVar<F> {
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        bits_to_field_elements_le(&self.0)
+    }
+}
+
+/// Little-endian bits of the secret scalar.
+#[derive(Clone)]
+pub struct SecretKeyVar<F: Field>(pub Vec<Boolean<F>>);
+
+impl<C, F> AllocVar<SecretKey<C>, F> for SecretKeyVar<F>
+where
+    C: CurveGroup,
+    F: PrimeField,
+{
+    fn new_variable<T: Borrow<SecretKey<C>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        alloc_scalar_bits_le(cs, f().map(|sk| sk.borrow().0), mode).map(Self)
+    }
+}
+
+impl<F: PrimeField> R1CSVar<F> for SecretKeyVar<F> {
+    /// The little-endian bits of the secret scalar.
+    type Value = Vec<bool>;
+
+    fn cs(&self) -> ConstraintSystemRef<F> {
+        self.0.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        self.0.value()
+    }
+}
+
+impl<F: PrimeField> EqGadget<F> for SecretKeyVar<F> {
+    fn is_eq(&self, other: &Self) -> Result<Boolean<F>, SynthesisError> {
+        self.0.is_eq(&other.0)
+    }
+}
+
+impl<F: PrimeField> CondSelectGadget<F> for SecretKeyVar<F> {
+    fn conditionally_select(
+        cond: &Boolean<F>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        true_value
+            .0
+            .iter()
+            .zip(&false_value.0)
+            .map(|(t, f)| cond.select(t, f))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+impl<F: PrimeField> ToBytesGadget<F> for SecretKeyVar<F> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<F>>, SynthesisError> {
+        Ok(bits_to_bytes_le(&self.0))
+    }
+}
+
+impl<F: PrimeField> ToConstraintFieldGadget<F> for SecretKeyVar<F> {
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        bits_to_field_elements_le(&self.0)
+    }
+}
+
+#[derive(Clone)]
+pub struct ParametersVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    pub generator: GG,
+    /// Window tables of `generator`, precomputed when the parameters are allocated as a constant.
+    pub generator_table: Option<FixedBaseTable<C>>,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+{
+    /// Computes `bits * generator`, using the fixed-base window tables when available.
+    pub fn scalar_mul_generator_le(&self, bits: &[Boolean<C::BaseField>]) -> Result<GG, SynthesisError> {
+        fixed_base_scalar_mul_le(&self.generator, self.generator_table.as_ref(), bits)
+    }
+}
+
+impl<C, GG> AllocVar<Parameters<C>, C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn new_variable<T: Borrow<Parameters<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let value = f().map(|g| g.borrow().generator);
+        let generator = GG::new_variable(cs, || value, mode)?;
+
+        // a constant generator lets scalar multiplications use precomputed lookup tables
+        let generator_table = match (mode, value) {
+            (AllocationMode::Constant, Ok(g)) => Some(window_tables::<C>(&g)),
+            _ => None,
+        };
+
+        Ok(Self {
+            generator,
+            generator_table,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> R1CSVar<C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    type Value = Parameters<C>;
+
+    fn cs(&self) -> ConstraintSystemRef<C::BaseField> {
+        self.generator.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(Parameters {
+            generator: self.generator.value()?.into_affine(),
+        })
+    }
+}
+
+impl<C, GG> EqGadget<C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    #[inline]
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        self.generator.is_eq(&other.generator)
+    }
+}
+
+impl<C, GG> CondSelectGadget<C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn conditionally_select(
+        cond: &Boolean<C::BaseField>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let generator = GG::conditionally_select(cond, &true_value.generator, &false_value.generator)?;
+
+        // the lookup tables survive only when the selected generator is known at compile time
+        let generator_table = match cond {
+            Boolean::Constant(true) => true_value.generator_table.clone(),
+            Boolean::Constant(false) => false_value.generator_table.clone(),
+            _ if true_value.generator_table == false_value.generator_table => {
+                true_value.generator_table.clone()
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            generator,
+            generator_table,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> ToBytesGadget<C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn to_bytes(&self) -> Result<Vec<UInt8<C::BaseField>>, SynthesisError> {
+        self.generator.to_bytes()
+    }
+}
+
+impl<C, GG> ToConstraintFieldGadget<C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField> + ToConstraintFieldGadget<C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<C::BaseField>>, SynthesisError> {
+        self.generator.to_constraint_field()
+    }
+}
+
 #[derive(Clone)]
-pub struct RandomnessVar<F: Field>(pub Vec<UInt8<F>>);
+pub struct PlaintextVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    pub plaintext: GG,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> AllocVar<Plaintext<C>, C::BaseField> for PlaintextVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn new_variable<T: Borrow<Plaintext<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let plaintext = GG::new_variable(cs, f, mode)?;
+        Ok(Self {
+            plaintext,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> R1CSVar<C::BaseField> for PlaintextVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    type Value = Plaintext<C>;
+
+    fn cs(&self) -> ConstraintSystemRef<C::BaseField> {
+        self.plaintext.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(self.plaintext.value()?.into_affine())
+    }
+}
+
+impl<C, GG> EqGadget<C::BaseField> for PlaintextVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    #[inline]
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        self.plaintext.is_eq(&other.plaintext)
+    }
+}
+
+impl<C, GG> CondSelectGadget<C::BaseField> for PlaintextVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn conditionally_select(
+        cond: &Boolean<C::BaseField>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self {
+            plaintext: GG::conditionally_select(cond, &true_value.plaintext, &false_value.plaintext)?,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> ToBytesGadget<C::BaseField> for PlaintextVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn to_bytes(&self) -> Result<Vec<UInt8<C::BaseField>>, SynthesisError> {
+        self.plaintext.to_bytes()
+    }
+}
+
+impl<C, GG> ToConstraintFieldGadget<C::BaseField> for PlaintextVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField> + ToConstraintFieldGadget<C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<C::BaseField>>, SynthesisError> {
+        self.plaintext.to_constraint_field()
+    }
+}
+
+#[derive(Clone)]
+pub struct PublicKeyVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    pub pk: GG,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> AllocVar<PublicKey<C>, C::BaseField> for PublicKeyVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn new_variable<T: Borrow<PublicKey<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let pk = GG::new_variable(cs, f, mode)?;
+        Ok(Self {
+            pk,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> R1CSVar<C::BaseField> for PublicKeyVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    type Value = PublicKey<C>;
+
+    fn cs(&self) -> ConstraintSystemRef<C::BaseField> {
+        self.pk.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(self.pk.value()?.into_affine())
+    }
+}
+
+impl<C, GG> EqGadget<C::BaseField> for PublicKeyVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    #[inline]
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        self.pk.is_eq(&other.pk)
+    }
+}
+
+impl<C, GG> CondSelectGadget<C::BaseField> for PublicKeyVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn conditionally_select(
+        cond: &Boolean<C::BaseField>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self {
+            pk: GG::conditionally_select(cond, &true_value.pk, &false_value.pk)?,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> ToBytesGadget<C::BaseField> for PublicKeyVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn to_bytes(&self) -> Result<Vec<UInt8<C::BaseField>>, SynthesisError> {
+        self.pk.to_bytes()
+    }
+}
+
+impl<C, GG> ToConstraintFieldGadget<C::BaseField> for PublicKeyVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField> + ToConstraintFieldGadget<C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<C::BaseField>>, SynthesisError> {
+        self.pk.to_constraint_field()
+    }
+}
+
+#[derive(Clone)]
+pub struct OutputVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    pub c1: GG,
+    pub c2: GG,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> AllocVar<Ciphertext<C>, C::BaseField> for OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn new_variable<T: Borrow<Ciphertext<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let prep = f().map(|g| *g.borrow());
+        let c1 = GG::new_variable(cs.clone(), || prep.map(|g| g.0), mode)?;
+        let c2 = GG::new_variable(cs.clone(), || prep.map(|g| g.1), mode)?;
+        Ok(Self {
+            c1,
+            c2,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    /// Component-wise sum; encrypts the sum of the plaintexts, as `Ciphertext::add` does natively.
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            c1: self.c1.clone() + &other.c1,
+            c2: self.c2.clone() + &other.c2,
+            _curve: PhantomData,
+        }
+    }
+
+    /// Component-wise difference; encrypts the difference of the plaintexts.
+    pub fn sub(&self, other: &Self) -> Self {
+        Self {
+            c1: self.c1.clone() - &other.c1,
+            c2: self.c2.clone() - &other.c2,
+            _curve: PhantomData,
+        }
+    }
+
+    /// Multiplies both components by the scalar with little-endian `bits`, e.g. a witness from
+    /// `alloc_scalar_bits_le`.
+    pub fn scalar_mul_le(&self, bits: &[Boolean<C::BaseField>]) -> Result<Self, SynthesisError> {
+        Ok(Self {
+            c1: self.c1.scalar_mul_le(bits.iter())?,
+            c2: self.c2.scalar_mul_le(bits.iter())?,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GC> EqGadget<C::BaseField> for OutputVar<C, GC>
+where
+    C: CurveGroup,
+    GC: CurveVar<C, C::BaseField>,
+{
+    #[inline]
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        self.c1.is_eq(&other.c1)?.and(&self.c2.is_eq(&other.c2)?)
+    }
+}
+
+impl<C, GG> R1CSVar<C::BaseField> for OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    type Value = Ciphertext<C>;
+
+    fn cs(&self) -> ConstraintSystemRef<C::BaseField> {
+        self.c1.cs().or(self.c2.cs())
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(Ciphertext(self.c1.value()?.into_affine(), self.c2.value()?.into_affine()))
+    }
+}
+
+impl<C, GG> CondSelectGadget<C::BaseField> for OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn conditionally_select(
+        cond: &Boolean<C::BaseField>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self {
+            c1: GG::conditionally_select(cond, &true_value.c1, &false_value.c1)?,
+            c2: GG::conditionally_select(cond, &true_value.c2, &false_value.c2)?,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> ToBytesGadget<C::BaseField> for OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn to_bytes(&self) -> Result<Vec<UInt8<C::BaseField>>, SynthesisError> {
+        let mut bytes = self.c1.to_bytes()?;
+        bytes.extend(self.c2.to_bytes()?);
+        Ok(bytes)
+    }
+}
 
-impl<C, F> AllocVar<Randomness<C>, F> for RandomnessVar<F>
+impl<C, GG> ToConstraintFieldGadget<C::BaseField> for OutputVar<C, GG>
 where
     C: CurveGroup,
-    F: PrimeField,
+    GG: CurveVar<C, C::BaseField> + ToConstraintFieldGadget<C::BaseField>,
+    C::BaseField: PrimeField,
 {
-    fn new_variable<T: Borrow<Randomness<C>>>(
-        cs: impl Into<Namespace<F>>,
-        f: impl FnOnce() -> Result<T, SynthesisError>,
-        mode: AllocationMode,
-    ) -> Result<Self, SynthesisError> {
-        let mut r = Vec::new();
-        let _ = &f()
-            .map(|b| b.borrow().0)
-            .unwrap_or(C::ScalarField::zero())
-            .serialize_compressed(&mut r)
-            .unwrap();
-        match mode {
-            AllocationMode::Constant => Ok(Self(UInt8::constant_vec(&r))),
-            AllocationMode::Input => UInt8::new_input_vec(cs, &r).map(Self),
-            AllocationMode::Witness => UInt8::new_witness_vec(cs, &r).map(Self),
-        }
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<C::BaseField>>, SynthesisError> {
+        let mut elements = self.c1.to_constraint_field()?;
+        elements.extend(self.c2.to_constraint_field()?);
+        Ok(elements)
     }
 }
 
+/// Multi-recipient ciphertext: a shared `c1` and one `c2` per public key.
 #[derive(Clone)]
-pub struct ParametersVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+pub struct MultiOutputVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
 {
-    pub generator: GG,
+    pub c1: GG,
+    pub c2: Vec<GG>,
     pub _curve: PhantomData<C>,
 }
 
-impl<C, GG> AllocVar<Parameters<C>, C::BaseField> for ParametersVar<C, GG>
+impl<C, GG> AllocVar<multi::MultiCiphertext<C>, C::BaseField> for MultiOutputVar<C, GG>
 where
     C: CurveGroup,
     GG: CurveVar<C, C::BaseField>,
 {
-    fn new_variable<T: Borrow<Parameters<C>>>(
+    fn new_variable<T: Borrow<multi::MultiCiphertext<C>>>(
         cs: impl Into<Namespace<C::BaseField>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
-        let generator = GG::new_variable(cs, || f().map(|g| g.borrow().generator), mode)?;
-        Ok(Self {
-            generator,
-            _curve: PhantomData,
-        })
+        // the number of recipients is part of the circuit shape, so the value is needed up front;
+        // circuits that know it from their public keys use `new_variable_with_recipients`
+        let prep = f()?.borrow().clone();
+        Self::new_variable_with_recipients(cs, prep.c2.len(), || Ok(prep), mode)
     }
 }
 
-#[derive(Clone)]
-pub struct PlaintextVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
-{
-    pub plaintext: GG,
-    pub _curve: PhantomData<C>,
-}
-
-impl<C, GG> AllocVar<Plaintext<C>, C::BaseField> for PlaintextVar<C, GG>
+impl<C, GG> MultiOutputVar<C, GG>
 where
     C: CurveGroup,
     GG: CurveVar<C, C::BaseField>,
 {
-    fn new_variable<T: Borrow<Plaintext<C>>>(
+    /// Allocates a ciphertext addressed to `recipients` keys. A missing assignment is passed on
+    /// to every component, as `OutputVar` does, so a setup without values still succeeds.
+    pub fn new_variable_with_recipients<T: Borrow<multi::MultiCiphertext<C>>>(
         cs: impl Into<Namespace<C::BaseField>>,
+        recipients: usize,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
-        let plaintext = GG::new_variable(cs, f, mode)?;
+        let ns = cs.into();
+        let cs = ns.cs();
+        let prep = f().map(|g| g.borrow().clone());
+        if prep.as_ref().is_ok_and(|p| p.c2.len() != recipients) {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let c1 = GG::new_variable(cs.clone(), || prep.as_ref().map(|p| p.c1).map_err(|e| *e), mode)?;
+        let c2 = (0..recipients)
+            .map(|i| GG::new_variable(cs.clone(), || prep.as_ref().map(|p| p.c2[i]).map_err(|e| *e), mode))
+            .collect::<Result<Vec<_>, _>>()?;
         Ok(Self {
-            plaintext,
+            c1,
+            c2,
             _curve: PhantomData,
         })
     }
 }
 
-#[derive(Clone)]
-pub struct PublicKeyVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+impl<C, GC> EqGadget<C::BaseField> for MultiOutputVar<C, GC>
+where
+    C: CurveGroup,
+    GC: CurveVar<C, C::BaseField>,
 {
-    pub pk: GG,
-    pub _curve: PhantomData<C>,
+    #[inline]
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        if self.c2.len() != other.c2.len() {
+            return Ok(Boolean::FALSE);
+        }
+        let mut result = self.c1.is_eq(&other.c1)?;
+        for (a, b) in self.c2.iter().zip(&other.c2) {
+            result = result.and(&a.is_eq(b)?)?;
+        }
+        Ok(result)
+    }
 }
 
-impl<C, GG> AllocVar<PublicKey<C>, C::BaseField> for PublicKeyVar<C, GG>
+impl<C, GG> R1CSVar<C::BaseField> for MultiOutputVar<C, GG>
 where
     C: CurveGroup,
     GG: CurveVar<C, C::BaseField>,
 {
-    fn new_variable<T: Borrow<PublicKey<C>>>(
-        cs: impl Into<Namespace<C::BaseField>>,
-        f: impl FnOnce() -> Result<T, SynthesisError>,
-        mode: AllocationMode,
-    ) -> Result<Self, SynthesisError> {
-        let pk = GG::new_variable(cs, f, mode)?;
-        Ok(Self {
-            pk,
-            _curve: PhantomData,
-        })
+    type Value = multi::MultiCiphertext<C>;
+
+    fn cs(&self) -> ConstraintSystemRef<C::BaseField> {
+        self.c2.iter().fold(self.c1.cs(), |cs, c2| cs.or(c2.cs()))
     }
-}
 
-#[derive(Clone)]
-pub struct OutputVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
-{
-    pub c1: GG,
-    pub c2: GG,
-    pub _curve: PhantomData<C>,
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(multi::MultiCiphertext {
+            c1: self.c1.value()?.into_affine(),
+            c2: C::normalize_batch(&self.c2.value()?),
+        })
+    }
 }
 
-impl<C, GG> AllocVar<Ciphertext<C>, C::BaseField> for OutputVar<C, GG>
+impl<C, GG> CondSelectGadget<C::BaseField> for MultiOutputVar<C, GG>
 where
     C: CurveGroup,
     GG: CurveVar<C, C::BaseField>,
 {
-    fn new_variable<T: Borrow<Ciphertext<C>>>(
-        cs: impl Into<Namespace<C::BaseField>>,
-        f: impl FnOnce() -> Result<T, SynthesisError>,
-        mode: AllocationMode,
+    /// Both ciphertexts must address the same number of recipients.
+    fn conditionally_select(
+        cond: &Boolean<C::BaseField>,
+        true_value: &Self,
+        false_value: &Self,
     ) -> Result<Self, SynthesisError> {
-        let ns = cs.into();
-        let cs = ns.cs();
-        let prep = f().map(|g| *g.borrow());
-        let c1 = GG::new_variable(cs.clone(), || prep.map(|g| g.borrow().0), mode)?;
-        let c2 = GG::new_variable(cs.clone(), || prep.map(|g| g.borrow().1), mode)?;
+        if true_value.c2.len() != false_value.c2.len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let c2 = true_value
+            .c2
+            .iter()
+            .zip(&false_value.c2)
+            .map(|(t, f)| GG::conditionally_select(cond, t, f))
+            .collect::<Result<Vec<_>, _>>()?;
         Ok(Self {
-            c1,
+            c1: GG::conditionally_select(cond, &true_value.c1, &false_value.c1)?,
             c2,
             _curve: PhantomData,
         })
     }
 }
 
-impl<C, GC> EqGadget<C::BaseField> for OutputVar<C, GC>
+impl<C, GG> ToBytesGadget<C::BaseField> for MultiOutputVar<C, GG>
 where
     C: CurveGroup,
-    GC: CurveVar<C, C::BaseField>,
+    GG: CurveVar<C, C::BaseField>,
 {
-    #[inline]
-    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
-        self.c1.is_eq(&other.c1)?.and(&self.c2.is_eq(&other.c2)?)
+    fn to_bytes(&self) -> Result<Vec<UInt8<C::BaseField>>, SynthesisError> {
+        let mut bytes = self.c1.to_bytes()?;
+        for c2 in &self.c2 {
+            bytes.extend(c2.to_bytes()?);
+        }
+        Ok(bytes)
     }
 }
 
+impl<C, GG> ToConstraintFieldGadget<C::BaseField> for MultiOutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField> + ToConstraintFieldGadget<C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<C::BaseField>>, SynthesisError> {
+        let mut elements = self.c1.to_constraint_field()?;
+        for c2 in &self.c2 {
+            elements.extend(c2.to_constraint_field()?);
+        }
+        Ok(elements)
+    }
+}
 
 #[derive(Clone)]
 pub struct ElGamalEncGadget<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
@@ -166,7 +812,7 @@ pub struct ElGamalEncGadget<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
 impl<C, GG> AsymmetricEncryptionGadget<ElGamal<C>, C::BaseField> for ElGamalEncGadget<C, GG>
 where
     C: CurveGroup,
-    GG: CurveVar<C, C::BaseField>,
+    GG: FixedBaseMulGadget<C>,
     C::BaseField: PrimeField,
 {
     type OutputVar = OutputVar<C, GG>;
@@ -181,21 +827,13 @@ where
         randomness: &Self::RandomnessVar,
         public_key: &Self::PublicKeyVar,
     ) -> Result<Self::OutputVar, SynthesisError> {
-        // flatten randomness to little-endian bit vector
-        let randomness = randomness
-            .0
-            .iter()
-            .flat_map(|b| b.to_bits_le().unwrap())
-            .collect::<Vec<_>>();
+        let randomness = &randomness.0;
 
         // compute s = randomness*pk
         let s = public_key.pk.clone().scalar_mul_le(randomness.iter())?;
 
         // compute c1 = randomness*generator
-        let c1 = parameters
-            .generator
-            .clone()
-            .scalar_mul_le(randomness.iter())?;
+        let c1 = parameters.scalar_mul_generator_le(randomness)?;
 
         // compute c2 = m + s
         let c2 = message.plaintext.clone() + s;
@@ -208,20 +846,120 @@ where
     }
 }
 
+impl<C, GG> AsymmetricDecryptionGadget<ElGamal<C>, C::BaseField> for ElGamalEncGadget<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+    C::BaseField: PrimeField,
+{
+    type SecretKeyVar = SecretKeyVar<C::BaseField>;
+
+    fn decrypt(
+        parameters: &Self::ParametersVar,
+        ciphertext: &Self::OutputVar,
+        secret_key: &Self::SecretKeyVar,
+        public_key: &Self::PublicKeyVar,
+    ) -> Result<Self::PlaintextVar, SynthesisError> {
+        // enforce pk = sk*generator
+        let pk = parameters.scalar_mul_generator_le(&secret_key.0)?;
+        pk.enforce_equal(&public_key.pk)?;
+
+        // compute m = c2 - sk*c1
+        let s = ciphertext.c1.clone().scalar_mul_le(secret_key.0.iter())?;
+        let plaintext = ciphertext.c2.clone() - s;
+
+        Ok(Self::PlaintextVar {
+            plaintext,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> ElGamalEncGadget<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+    C::BaseField: PrimeField,
+{
+    /// Encrypts one plaintext to every key in `public_keys` with the same randomness, so the
+    /// resulting `c2` components provably carry the same message.
+    pub fn encrypt_multi(
+        parameters: &ParametersVar<C, GG>,
+        message: &PlaintextVar<C, GG>,
+        randomness: &RandomnessVar<C::BaseField>,
+        public_keys: &[PublicKeyVar<C, GG>],
+    ) -> Result<MultiOutputVar<C, GG>, SynthesisError> {
+        let randomness = &randomness.0;
+
+        // compute c1 = randomness*generator
+        let c1 = parameters.scalar_mul_generator_le(randomness)?;
+
+        // compute c2_i = m + randomness*pk_i
+        let c2 = public_keys
+            .iter()
+            .map(|pk| {
+                let s = pk.pk.clone().scalar_mul_le(randomness.iter())?;
+                Ok(message.plaintext.clone() + s)
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        Ok(MultiOutputVar {
+            c1,
+            c2,
+            _curve: PhantomData,
+        })
+    }
+
+    /// Computes `ciphertext + Enc(pk, 0; randomness)`, a fresh ciphertext of the same plaintext.
+    pub fn rerandomize(
+        parameters: &ParametersVar<C, GG>,
+        ciphertext: &OutputVar<C, GG>,
+        randomness: &RandomnessVar<C::BaseField>,
+        public_key: &PublicKeyVar<C, GG>,
+    ) -> Result<OutputVar<C, GG>, SynthesisError> {
+        let randomness = &randomness.0;
+
+        // compute c1' = c1 + randomness*generator
+        let c1 = ciphertext.c1.clone() + parameters.scalar_mul_generator_le(randomness)?;
+
+        // compute c2' = c2 + randomness*pk
+        let c2 = ciphertext.c2.clone() + public_key.pk.clone().scalar_mul_le(randomness.iter())?;
+
+        Ok(OutputVar {
+            c1,
+            c2,
+            _curve: PhantomData,
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use ark_std::{test_rng, UniformRand};
 
-    use ark_ed_on_bn254::{constraints::EdwardsVar, EdwardsProjective, Fq};
+    use ark_ed_on_bn254::{constraints::EdwardsVar, EdwardsProjective, Fq, Fr};
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_r1cs_std::prelude::{AllocationMode, Boolean, CondSelectGadget, ToBytesGadget};
+    use ark_r1cs_std::ToConstraintFieldGadget;
+    use ark_relations::r1cs::{SynthesisError, SynthesisMode};
 
-    use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
-    use crate::gadgets::public_encryptions::elgamal::{constraints::ElGamalEncGadget, ElGamal, Randomness};
+    use crate::gadgets::public_encryptions::constraints::{AsymmetricDecryptionGadget, AsymmetricEncryptionGadget};
+    use crate::gadgets::public_encryptions::elgamal::constraints::{
+        alloc_scalar_bits_le, enforce_canonical_le, scalar_from_bits_le, ElGamalEncGadget, MultiOutputVar, OutputVar, ParametersVar, PlaintextVar, PublicKeyVar,
+        RandomnessVar, SecretKeyVar,
+    };
+    use crate::gadgets::public_encryptions::elgamal::multi::MultiCiphertext;
+    use crate::gadgets::public_encryptions::elgamal::{Ciphertext, ElGamal, Randomness};
     use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
     use ark_relations::r1cs::ConstraintSystem;
     use ark_r1cs_std::R1CSVar;
     use ark_r1cs_std::alloc::AllocVar;
     use ark_r1cs_std::eq::EqGadget;
 
+    // constraints spent on `c1 = r*G` alone on ed_on_bn254
+    const FIXED_BASE_CONSTRAINTS: usize = 831;
+    const VARIABLE_BASE_CONSTRAINTS: usize = 1250;
+
     #[test]
     fn test_elgamal_gadget() {
         let rng = &mut test_rng();
@@ -280,4 +1018,304 @@ mod test {
         assert_eq!(primitive_result.1, result_var.c2.value().unwrap());
         assert!(cs.is_satisfied().unwrap());
     }
+
+    fn generator_constraints(fixed_base: bool) -> usize {
+        let rng = &mut test_rng();
+
+        type MyEnc = ElGamal<EdwardsProjective>;
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg = EdwardsProjective::rand(rng).into();
+        let randomness = Randomness::rand(rng);
+        let primitive_result = MyEnc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let mut parameters_var = ParametersVar::<EdwardsProjective, EdwardsVar>::new_constant(cs.clone(), &parameters).unwrap();
+        if !fixed_base {
+            parameters_var.generator_table = None;
+        }
+        let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+
+        let before = cs.num_constraints();
+        let c1 = parameters_var.scalar_mul_generator_le(&randomness_var.0).unwrap();
+        let used = cs.num_constraints() - before;
+
+        assert_eq!(primitive_result.0, c1.value().unwrap());
+        assert!(cs.is_satisfied().unwrap());
+        used
+    }
+
+    #[test]
+    fn test_elgamal_fixed_base_constraints() {
+        let fixed = generator_constraints(true);
+        let variable = generator_constraints(false);
+
+        // regression guard: update these numbers deliberately when the gadget changes
+        assert_eq!(FIXED_BASE_CONSTRAINTS, fixed);
+        assert_eq!(VARIABLE_BASE_CONSTRAINTS, variable);
+        assert!(fixed < variable);
+    }
+
+    #[test]
+    fn test_randomness_var_allocation() {
+        let rng = &mut test_rng();
+        let randomness = Randomness::<EdwardsProjective>::rand(rng);
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+        assert_eq!(Fr::MODULUS_BIT_SIZE as usize, randomness_var.0.len());
+        assert_eq!(randomness.0.into_bigint().to_bits_le()[..251], randomness_var.0.value().unwrap()[..]);
+        assert!(cs.is_satisfied().unwrap());
+
+        // a missing assignment is an error rather than a silent zero
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let missing = <RandomnessVar<Fq> as AllocVar<Randomness<EdwardsProjective>, Fq>>::new_witness(
+            cs.clone(),
+            || Err::<Randomness<EdwardsProjective>, _>(SynthesisError::AssignmentMissing),
+        );
+        assert!(matches!(missing, Err(SynthesisError::AssignmentMissing)));
+    }
+
+    #[test]
+    fn test_non_canonical_scalar_rejected() {
+        for (value, canonical) in [(Fr::MODULUS, false), (Fr::from(-1).into_bigint(), true)] {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let bits = value.to_bits_le()[..Fr::MODULUS_BIT_SIZE as usize]
+                .iter()
+                .map(|b| Boolean::new_witness(cs.clone(), || Ok(*b)).unwrap())
+                .collect::<Vec<_>>();
+            enforce_canonical_le::<Fr, Fq>(&bits).unwrap();
+            assert_eq!(canonical, cs.is_satisfied().unwrap());
+
+            // reading the value back reports non-canonical bits as malformed, not unsatisfied
+            let value = scalar_from_bits_le::<Fr>(&bits.value().unwrap());
+            if canonical {
+                assert_eq!(Fr::from(-1), value.unwrap());
+            } else {
+                assert!(matches!(value, Err(SynthesisError::AssignmentMissing)));
+            }
+        }
+        assert!(matches!(scalar_from_bits_le::<Fr>(&[true; 8]), Err(SynthesisError::AssignmentMissing)));
+    }
+
+    #[test]
+    fn test_elgamal_decryption_gadget() {
+        let rng = &mut test_rng();
+
+        type MyEnc = ElGamal<EdwardsProjective>;
+        type MyGadget = ElGamalEncGadget<EdwardsProjective, EdwardsVar>;
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let (pk, sk) = MyEnc::keygen(&parameters, rng).unwrap();
+        let (_, other_sk) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg = EdwardsProjective::rand(rng).into();
+        let ct = MyEnc::encrypt(&parameters, &pk, &msg, &Randomness::rand(rng)).unwrap();
+
+        for (secret, satisfied) in [(&sk, true), (&other_sk, false)] {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let parameters_var = ParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+            let pk_var = PublicKeyVar::new_input(cs.clone(), || Ok(&pk)).unwrap();
+            let ct_var = OutputVar::new_input(cs.clone(), || Ok(&ct)).unwrap();
+            let sk_var = SecretKeyVar::new_witness(cs.clone(), || Ok(secret)).unwrap();
+
+            let msg_var = MyGadget::decrypt(&parameters_var, &ct_var, &sk_var, &pk_var).unwrap();
+            if satisfied {
+                assert_eq!(msg, msg_var.plaintext.value().unwrap());
+            }
+            assert_eq!(satisfied, cs.is_satisfied().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_elgamal_rerandomize_gadget() {
+        let rng = &mut test_rng();
+
+        type MyEnc = ElGamal<EdwardsProjective>;
+        type MyGadget = ElGamalEncGadget<EdwardsProjective, EdwardsVar>;
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg = EdwardsProjective::rand(rng).into();
+        let ct = MyEnc::encrypt(&parameters, &pk, &msg, &Randomness::rand(rng)).unwrap();
+        let r_prime = Randomness::rand(rng);
+        let rerandomized = ct.rerandomize(&parameters, &pk, &r_prime);
+        let unrelated = MyEnc::encrypt(&parameters, &pk, &msg, &Randomness::rand(rng)).unwrap();
+
+        for (claimed, satisfied) in [(rerandomized, true), (unrelated, false)] {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let parameters_var = ParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+            let pk_var = PublicKeyVar::new_input(cs.clone(), || Ok(&pk)).unwrap();
+            let ct_var = OutputVar::new_input(cs.clone(), || Ok(&ct)).unwrap();
+            let claimed_var = OutputVar::new_input(cs.clone(), || Ok(&claimed)).unwrap();
+            let r_var = RandomnessVar::new_witness(cs.clone(), || Ok(&r_prime)).unwrap();
+
+            let result_var = MyGadget::rerandomize(&parameters_var, &ct_var, &r_var, &pk_var).unwrap();
+            result_var.enforce_equal(&claimed_var).unwrap();
+            if satisfied {
+                assert_eq!(rerandomized.0, result_var.c1.value().unwrap());
+                assert_eq!(rerandomized.1, result_var.c2.value().unwrap());
+            }
+            assert_eq!(satisfied, cs.is_satisfied().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_elgamal_homomorphic_gadgets() {
+        let rng = &mut test_rng();
+
+        type MyEnc = ElGamal<EdwardsProjective>;
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let mut encrypt = || {
+            let msg = EdwardsProjective::rand(rng).into();
+            MyEnc::encrypt(&parameters, &pk, &msg, &Randomness::rand(rng)).unwrap()
+        };
+        let (ct_old, ct_delta, unrelated) = (encrypt(), encrypt(), encrypt());
+        let k = Fr::rand(rng);
+
+        // ct_new = (ct_old + ct_delta) * k - ct_delta, against the native operations
+        let check = |claimed, k: Fr| {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let old_var = OutputVar::<EdwardsProjective, EdwardsVar>::new_input(cs.clone(), || Ok(&ct_old)).unwrap();
+            let delta_var = OutputVar::new_input(cs.clone(), || Ok(&ct_delta)).unwrap();
+            let claimed_var = OutputVar::new_input(cs.clone(), || Ok(&claimed)).unwrap();
+            let k_var = alloc_scalar_bits_le(cs.clone(), Ok(k), AllocationMode::Witness).unwrap();
+
+            let result_var = old_var.add(&delta_var).scalar_mul_le(&k_var).unwrap().sub(&delta_var);
+            result_var.enforce_equal(&claimed_var).unwrap();
+            cs.is_satisfied().unwrap()
+        };
+
+        let ct_new = (ct_old + ct_delta) * k - ct_delta;
+        assert!(check(ct_new, k));
+
+        // a tampered result, or a witness scalar that does not produce it, is rejected
+        assert!(!check(ct_new + unrelated, k));
+        assert!(!check(Ciphertext(ct_new.0, unrelated.1), k));
+        assert!(!check(ct_new, k + Fr::from(1u64)));
+    }
+
+    #[test]
+    fn test_elgamal_var_traits() {
+        let rng = &mut test_rng();
+
+        type MyEnc = ElGamal<EdwardsProjective>;
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let other_parameters = MyEnc::setup(rng).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let (other_pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg = EdwardsProjective::rand(rng).into();
+        let randomness = Randomness::rand(rng);
+        let ct = MyEnc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+        let multi_ct = MyEnc::encrypt_multi(&parameters, &[pk, other_pk], &msg, &randomness).unwrap();
+
+        type C = EdwardsProjective;
+        type GG = EdwardsVar;
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let parameters_var = ParametersVar::<C, GG>::new_constant(cs.clone(), &parameters).unwrap();
+        let other_parameters_var = ParametersVar::<C, GG>::new_constant(cs.clone(), &other_parameters).unwrap();
+        let pk_var = PublicKeyVar::<C, GG>::new_witness(cs.clone(), || Ok(&pk)).unwrap();
+        let other_pk_var = PublicKeyVar::<C, GG>::new_witness(cs.clone(), || Ok(&other_pk)).unwrap();
+        let msg_var = PlaintextVar::<C, GG>::new_witness(cs.clone(), || Ok(&msg)).unwrap();
+        let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+        let ct_var = OutputVar::<C, GG>::new_witness(cs.clone(), || Ok(&ct)).unwrap();
+        let multi_var = MultiOutputVar::<C, GG>::new_witness(cs.clone(), || Ok(&multi_ct)).unwrap();
+
+        assert_eq!(parameters, parameters_var.value().unwrap());
+        assert_eq!(pk, pk_var.value().unwrap());
+        assert_eq!(msg, msg_var.value().unwrap());
+        assert_eq!(ct, ct_var.value().unwrap());
+        assert_eq!(multi_ct, multi_var.value().unwrap());
+
+        // select the second key with a witness bit
+        let cond = Boolean::new_witness(cs.clone(), || Ok(false)).unwrap();
+        let selected = PublicKeyVar::conditionally_select(&cond, &pk_var, &other_pk_var).unwrap();
+        assert_eq!(other_pk, selected.value().unwrap());
+        selected.enforce_equal(&other_pk_var).unwrap();
+        selected.enforce_not_equal(&pk_var).unwrap();
+
+        // lookup tables are kept for a constant choice and dropped for a witness one
+        let kept = ParametersVar::conditionally_select(&Boolean::TRUE, &parameters_var, &other_parameters_var).unwrap();
+        assert!(kept.generator_table.is_some());
+        let dropped = ParametersVar::conditionally_select(&cond, &parameters_var, &other_parameters_var).unwrap();
+        assert!(dropped.generator_table.is_none());
+        assert_eq!(other_parameters, dropped.value().unwrap());
+
+        assert_eq!(
+            vec![ct.0.x, ct.0.y, ct.1.x, ct.1.y],
+            ct_var.to_constraint_field().unwrap().value().unwrap()
+        );
+        assert_eq!(6, multi_var.to_constraint_field().unwrap().len());
+
+        let randomness_bytes = randomness.0.into_bigint().to_bytes_le();
+        assert_eq!(randomness_bytes, randomness_var.to_bytes().unwrap().value().unwrap());
+        assert_eq!(
+            vec![Fq::from_le_bytes_mod_order(&randomness_bytes)],
+            randomness_var.to_constraint_field().unwrap().value().unwrap()
+        );
+
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_elgamal_multi_gadget() {
+        let rng = &mut test_rng();
+
+        type MyEnc = ElGamal<EdwardsProjective>;
+        type MyGadget = ElGamalEncGadget<EdwardsProjective, EdwardsVar>;
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let pks = (0..3)
+            .map(|_| MyEnc::keygen(&parameters, rng).unwrap().0)
+            .collect::<Vec<_>>();
+        let msg = EdwardsProjective::rand(rng).into();
+        let randomness = Randomness::rand(rng);
+        let primitive_result = MyEnc::encrypt_multi(&parameters, &pks, &msg, &randomness).unwrap();
+
+        let satisfied = |expected: &MultiCiphertext<EdwardsProjective>| {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+            let parameters_var = ParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+            let msg_var = PlaintextVar::new_witness(cs.clone(), || Ok(&msg)).unwrap();
+            let pk_vars = pks
+                .iter()
+                .map(|pk| PublicKeyVar::new_input(cs.clone(), || Ok(pk)).unwrap())
+                .collect::<Vec<_>>();
+
+            let result_var =
+                MyGadget::encrypt_multi(&parameters_var, &msg_var, &randomness_var, &pk_vars).unwrap();
+
+            let expected_var =
+                MultiOutputVar::new_variable_with_recipients(cs.clone(), pks.len(), || Ok(expected), AllocationMode::Input)
+                    .unwrap();
+            expected_var.enforce_equal(&result_var).unwrap();
+            cs.is_satisfied().unwrap()
+        };
+        assert!(satisfied(&primitive_result));
+
+        // a ciphertext whose second recipient got a different message is rejected
+        let mut tampered = primitive_result.clone();
+        let other = MyEnc::encrypt(&parameters, &pks[1], &EdwardsProjective::rand(rng).into(), &randomness).unwrap();
+        tampered.c2[1] = other.1;
+        assert!(!satisfied(&tampered));
+
+        // a setup allocates the ciphertext without a value; a proving run reports it missing
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        cs.set_mode(SynthesisMode::Setup);
+        let missing = || Err::<MultiCiphertext<EdwardsProjective>, _>(SynthesisError::AssignmentMissing);
+        let var = MultiOutputVar::<EdwardsProjective, EdwardsVar>::new_variable_with_recipients(cs.clone(), 3, missing, AllocationMode::Input)
+            .unwrap();
+        assert_eq!(3, var.c2.len());
+        assert_eq!(1 + 2 * 4, cs.num_instance_variables());
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        assert!(MultiOutputVar::<EdwardsProjective, EdwardsVar>::new_variable_with_recipients(cs.clone(), 3, missing, AllocationMode::Input)
+            .is_err());
+        assert!(MultiOutputVar::<EdwardsProjective, EdwardsVar>::new_variable_with_recipients(cs, 2, || Ok(&primitive_result), AllocationMode::Input)
+            .is_err());
+    }
 }
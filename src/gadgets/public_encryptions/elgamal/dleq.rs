@@ -0,0 +1,145 @@
+use std::ops::Mul;
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use super::{Ciphertext, ElGamal, Parameters, Plaintext, PublicKey, SecretKey};
+use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+const DOMAIN: &[u8] = b"arkworks-study/elgamal/dleq/v1";
+
+/// Chaum-Pedersen proof that `log_g(x) == log_h(y)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DleqProof<C: CurveGroup> {
+    pub a: C::Affine,
+    pub b: C::Affine,
+    pub z: C::ScalarField,
+}
+
+/// Fiat-Shamir challenge over the full statement and the prover's commitments.
+fn challenge<C: CurveGroup>(points: &[C::Affine]) -> C::ScalarField {
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN);
+    for point in points {
+        let mut bytes = Vec::new();
+        point.serialize_compressed(&mut bytes).unwrap();
+        hasher.update(bytes);
+    }
+    C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+/// Proves knowledge of `secret` with `x = secret*g` and `y = secret*h`.
+pub fn prove_dleq<C: CurveGroup, R: Rng>(
+    g: &C::Affine,
+    h: &C::Affine,
+    secret: &C::ScalarField,
+    rng: &mut R,
+) -> DleqProof<C> {
+    let x = g.mul(secret).into_affine();
+    let y = h.mul(secret).into_affine();
+
+    let w = C::ScalarField::rand(rng);
+    let a = g.mul(w).into_affine();
+    let b = h.mul(w).into_affine();
+
+    let e = challenge::<C>(&[*g, x, *h, y, a, b]);
+    let z = w + e * secret;
+
+    DleqProof { a, b, z }
+}
+
+pub fn verify_dleq<C: CurveGroup>(
+    g: &C::Affine,
+    x: &C::Affine,
+    h: &C::Affine,
+    y: &C::Affine,
+    proof: &DleqProof<C>,
+) -> bool {
+    let e = challenge::<C>(&[*g, *x, *h, *y, proof.a, proof.b]);
+
+    g.mul(proof.z) == proof.a + x.mul(e) && h.mul(proof.z) == proof.b + y.mul(e)
+}
+
+/// Decrypts `ciphertext` and proves that `c2 - m = sk*c1` for the `sk` behind `pk`.
+pub fn prove_decryption<C: CurveGroup, R: Rng>(
+    pp: &Parameters<C>,
+    sk: &SecretKey<C>,
+    ciphertext: &Ciphertext<C>,
+    rng: &mut R,
+) -> Result<(Plaintext<C>, DleqProof<C>), ark_crypto_primitives::Error> {
+    let m = ElGamal::<C>::decrypt(pp, sk, ciphertext)?;
+    let proof = prove_dleq::<C, R>(&pp.generator, &ciphertext.0, &sk.0, rng);
+    Ok((m, proof))
+}
+
+/// Checks that `m` is the decryption of `ciphertext` under the secret key of `pk`.
+pub fn verify_decryption<C: CurveGroup>(
+    pp: &Parameters<C>,
+    pk: &PublicKey<C>,
+    ciphertext: &Ciphertext<C>,
+    m: &Plaintext<C>,
+    proof: &DleqProof<C>,
+) -> bool {
+    let shared = (ciphertext.1.into_group() - m).into_affine();
+    verify_dleq(&pp.generator, pk, &ciphertext.0, &shared, proof)
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::CurveGroup;
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{prove_decryption, verify_decryption, DleqProof};
+    use crate::gadgets::public_encryptions::elgamal::{ElGamal, Randomness};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+
+    #[test]
+    fn test_decryption_proof() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let msg = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+
+        let (m, proof) = prove_decryption(&parameter, &sk, &cipher, rng).unwrap();
+        assert_eq!(msg, m);
+        assert!(verify_decryption(&parameter, &pk, &cipher, &m, &proof));
+    }
+
+    #[test]
+    fn test_decryption_proof_rejects_tampering() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+        let (other_pk, _) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let msg = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+        let other_cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &Randomness::rand(rng)).unwrap();
+
+        let (m, proof) = prove_decryption(&parameter, &sk, &cipher, rng).unwrap();
+
+        // wrong plaintext
+        let wrong_m = (m + C::rand(rng)).into_affine();
+        assert!(!verify_decryption(&parameter, &pk, &cipher, &wrong_m, &proof));
+
+        // wrong key or ciphertext
+        assert!(!verify_decryption(&parameter, &other_pk, &cipher, &m, &proof));
+        assert!(!verify_decryption(&parameter, &pk, &other_cipher, &m, &proof));
+
+        // modified response
+        let forged = DleqProof::<C> { z: proof.z + <C as ark_ec::Group>::ScalarField::from(1u64), ..proof };
+        assert!(!verify_decryption(&parameter, &pk, &cipher, &m, &forged));
+    }
+}
@@ -0,0 +1,823 @@
+//! ElGamal gadget for curves whose base field is not the constraint field, e.g. BN254 G1
+//! inside a BN254 `Fr` circuit. Coordinates are emulated with `NonNativeFieldVar`.
+//!
+//! Points are affine and never the identity. Additions are incomplete: adding a point to itself
+//! or to its negation leaves the circuit unsatisfied instead of producing a wrong result, so the
+//! gadget stays sound for adversarial inputs and honest inputs hit it with negligible probability.
+
+use std::{borrow::Borrow, marker::PhantomData};
+
+use ark_ec::short_weierstrass::{Affine, Projective, SWCurveConfig};
+use ark_ec::{AffineRepr, CurveConfig, CurveGroup};
+use ark_ff::{BitIteratorBE, Field, PrimeField};
+use ark_r1cs_std::fields::nonnative::{AllocatedNonNativeFieldVar, NonNativeFieldVar};
+use ark_r1cs_std::fields::nonnative::params::OptimizationType;
+use ark_r1cs_std::prelude::{AllocVar, AllocationMode, Boolean, CondSelectGadget, EqGadget, FieldVar};
+use ark_r1cs_std::R1CSVar;
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
+
+use crate::gadgets::curves::{window_tables, FixedBaseTable, WINDOW};
+use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+
+use super::constraints::RandomnessVar;
+use super::{hash_to_curve, Ciphertext, ElGamal, Parameters};
+
+/// Domain separation string for the accumulator offset used by scalar multiplications.
+pub const OFFSET_DOMAIN: &[u8] = b"arkworks-study/elgamal/emulated/offset/v1";
+
+type BaseFieldVar<P, F> = NonNativeFieldVar<<P as CurveConfig>::BaseField, F>;
+
+/// The point every scalar multiplication starts its accumulator from.
+pub fn accumulator_offset<P: SWCurveConfig>() -> Affine<P> {
+    hash_to_curve::<Projective<P>>(OFFSET_DOMAIN, b"offset")
+}
+
+/// Limbs of `value` in the order `NonNativeFieldVar::new_input` allocates them.
+fn limbs<P, F>(value: &P::BaseField) -> Vec<F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    AllocatedNonNativeFieldVar::<P::BaseField, F>::get_limbs_representations(value, OptimizationType::Constraints)
+        .expect("limb decomposition of a field element cannot fail")
+}
+
+/// The public inputs that allocating `point` with `new_input` expects: the limbs of `x`, then of `y`.
+pub fn point_public_inputs<P, F>(point: &Affine<P>) -> Vec<F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    let mut inputs = limbs::<P, F>(&point.x);
+    inputs.extend(limbs::<P, F>(&point.y));
+    inputs
+}
+
+/// The public inputs for a ciphertext allocated with `new_input`.
+pub fn ciphertext_public_inputs<P, F>(ciphertext: &Ciphertext<Projective<P>>) -> Vec<F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    let mut inputs = point_public_inputs::<P, F>(&ciphertext.0);
+    inputs.extend(point_public_inputs::<P, F>(&ciphertext.1));
+    inputs
+}
+
+/// A non-identity affine point with emulated coordinates.
+pub struct EmulatedPointVar<P: SWCurveConfig, F: PrimeField>
+where
+    P::BaseField: PrimeField,
+{
+    pub x: BaseFieldVar<P, F>,
+    pub y: BaseFieldVar<P, F>,
+}
+
+// derived `Clone` would require `P: Clone`
+impl<P, F> Clone for EmulatedPointVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn clone(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: self.y.clone(),
+        }
+    }
+}
+
+impl<P, F> EmulatedPointVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    pub fn constant(point: Affine<P>) -> Self {
+        Self {
+            x: BaseFieldVar::<P, F>::constant(point.x),
+            y: BaseFieldVar::<P, F>::constant(point.y),
+        }
+    }
+
+    /// Computes `self + other`; unsatisfiable when `self = ±other`.
+    pub fn add(&self, other: &Self) -> Result<Self, SynthesisError> {
+        // the inverse also enforces x1 != x2, without which lambda would be unconstrained
+        let lambda = (&other.y - &self.y) * (&other.x - &self.x).inverse()?;
+        let x = lambda.square()? - &self.x - &other.x;
+        let y = lambda * (&self.x - &x) - &self.y;
+        Ok(Self { x, y })
+    }
+
+    /// Computes `2 * self`.
+    pub fn double(&self) -> Result<Self, SynthesisError> {
+        // lambda * 2y = 3x^2 + a; y is never zero because the group has no points of order two
+        let numerator = self.x.square()? * P::BaseField::from(3u64) + P::COEFF_A;
+        let lambda = BaseFieldVar::<P, F>::new_witness(self.x.cs(), || {
+            let y2 = self.y.value()?.double();
+            Ok(numerator.value()? * y2.inverse().ok_or(SynthesisError::DivisionByZero)?)
+        })?;
+        lambda.mul_equals(&self.y.double()?, &numerator)?;
+
+        let x = lambda.square()? - self.x.double()?;
+        let y = lambda * (&self.x - &x) - &self.y;
+        Ok(Self { x, y })
+    }
+
+    /// Re-allocates `self` with its coordinates in normal form. Selecting between a reduced
+    /// coordinate, e.g. of an input, and one left unreduced by point arithmetic does not keep
+    /// track of its bounds, which can leave honest circuits unsatisfied, so table entries go
+    /// through here first.
+    fn normalize(&self) -> Result<Self, SynthesisError> {
+        let x = BaseFieldVar::<P, F>::new_witness(self.cs(), || self.x.value())?;
+        let y = BaseFieldVar::<P, F>::new_witness(self.cs(), || self.y.value())?;
+        let point = Self { x, y };
+        point.enforce_equal(self)?;
+        Ok(point)
+    }
+
+    /// Adds `table[k]` for the window whose little-endian `bits` spell `k`, or nothing when
+    /// `k = 0`. `table[0]` only keeps the skipped addition well defined, so it must not be the
+    /// identity either.
+    fn add_window(&self, table: &[Self], bits: &[Boolean<F>]) -> Result<Self, SynthesisError> {
+        let position = bits.iter().rev().cloned().collect::<Vec<_>>();
+        let entry = Self::conditionally_select_power_of_two_vector(&position, &table[..1 << bits.len()])?;
+        let sum = self.add(&entry)?;
+        Boolean::kary_or(bits)?.select(&sum, self)
+    }
+
+    /// Computes `bits * self` for little-endian `bits`; unsatisfiable when the scalar is zero.
+    /// `offset` is the `accumulator_offset`.
+    pub fn scalar_mul_le(&self, bits: &[Boolean<F>], offset: &Affine<P>) -> Result<Self, SynthesisError> {
+        // `k * self` for every window value `k`, with `self` standing in for `k = 0`
+        let mut table = vec![self.clone(), self.clone(), self.double()?.normalize()?];
+        while table.len() < 1 << WINDOW {
+            table.push(table[table.len() - 1].add(self)?.normalize()?);
+        }
+
+        // start from a point of unknown discrete log so no intermediate sum hits the identity
+        let mut acc = Self::constant(*offset);
+        for bits in bits.chunks(WINDOW).rev() {
+            for _ in bits {
+                acc = acc.double()?;
+            }
+            acc = acc.add_window(&table, bits)?;
+        }
+
+        let correction = -(*offset * P::ScalarField::from(2u64).pow([bits.len() as u64]));
+        acc.add(&Self::constant(correction.into_affine()))
+    }
+
+    /// Computes `k * self` for a constant `k > 0` given by its little-endian limbs.
+    fn mul_by_constant(&self, k: &[u64]) -> Result<Self, SynthesisError> {
+        let mut bits = BitIteratorBE::without_leading_zeros(k);
+        bits.next();
+
+        let mut acc = self.clone();
+        for bit in bits {
+            acc = acc.double()?;
+            if bit {
+                acc = acc.add(self)?;
+            }
+        }
+        Ok(acc)
+    }
+
+    // allocates `(x, y)` and, unless constant, enforces `y^2 = x^3 + ax + b`
+    fn new_on_curve(
+        cs: ConstraintSystemRef<F>,
+        value: Result<Affine<P>, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let x = BaseFieldVar::<P, F>::new_variable(cs.clone(), || value.map(|p| p.x), mode)?;
+        let y = BaseFieldVar::<P, F>::new_variable(cs.clone(), || value.map(|p| p.y), mode)?;
+
+        // the identity is encoded as (0, 0) and fails this check
+        if mode != AllocationMode::Constant {
+            let rhs = (x.square()? + P::COEFF_A) * &x + P::COEFF_B;
+            y.mul_equals(&y, &rhs)?;
+        }
+
+        Ok(Self { x, y })
+    }
+
+    /// Computes `bits * self`, through `table` from `window_tables` when available. A table
+    /// shorter than `bits` would drop the high bits, so it falls back to `scalar_mul_le` instead.
+    pub fn fixed_base_scalar_mul_le(
+        &self,
+        table: Option<&FixedBaseTable<Projective<P>>>,
+        bits: &[Boolean<F>],
+        offset: &Affine<P>,
+    ) -> Result<Self, SynthesisError> {
+        match table {
+            Some(table) if table.len() * WINDOW >= bits.len() => {
+                Self::precomputed_base_scalar_mul_le(bits, table, offset)
+            }
+            _ => self.scalar_mul_le(bits, offset),
+        }
+    }
+
+    /// Computes `bits * base` for a constant `base` given by its `window_tables`.
+    pub fn precomputed_base_scalar_mul_le(
+        bits: &[Boolean<F>],
+        table: &FixedBaseTable<Projective<P>>,
+        offset: &Affine<P>,
+    ) -> Result<Self, SynthesisError> {
+        let mut acc = Self::constant(*offset);
+        for (entries, bits) in table.iter().zip(bits.chunks(WINDOW)) {
+            // the identity at `k = 0` has no affine coordinates, so `k = 1` stands in for it
+            let entries = std::iter::once(entries[1])
+                .chain(entries[1..].iter().copied())
+                .map(Self::constant)
+                .collect::<Vec<_>>();
+            acc = acc.add_window(&entries, bits)?;
+        }
+
+        acc.add(&Self::constant((-offset.into_group()).into_affine()))
+    }
+}
+
+impl<P, F> AllocVar<Affine<P>, F> for EmulatedPointVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn new_variable<T: Borrow<Affine<P>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let value = f().map(|p| *p.borrow());
+        let point = Self::new_on_curve(cs.clone(), value, mode)?;
+
+        // with a cofactor h, the curve also has points outside the subgroup of order r. The
+        // prover supplies Q = h^{-1} * P on the curve, and P = h * Q lies in h * E, which is
+        // that subgroup because gcd(h, r) = 1
+        if mode != AllocationMode::Constant && !P::cofactor_is_one() {
+            let preimage = value.map(|p| (p * P::COFACTOR_INV).into_affine());
+            let q = Self::new_on_curve(cs, preimage, AllocationMode::Witness)?;
+            q.mul_by_constant(P::COFACTOR)?.enforce_equal(&point)?;
+        }
+
+        Ok(point)
+    }
+}
+
+impl<P, F> R1CSVar<F> for EmulatedPointVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    type Value = Affine<P>;
+
+    fn cs(&self) -> ConstraintSystemRef<F> {
+        self.x.cs().or(self.y.cs())
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(Affine::new_unchecked(self.x.value()?, self.y.value()?))
+    }
+}
+
+impl<P, F> EqGadget<F> for EmulatedPointVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn is_eq(&self, other: &Self) -> Result<Boolean<F>, SynthesisError> {
+        self.x.is_eq(&other.x)?.and(&self.y.is_eq(&other.y)?)
+    }
+}
+
+impl<P, F> CondSelectGadget<F> for EmulatedPointVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn conditionally_select(
+        cond: &Boolean<F>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self {
+            x: cond.select(&true_value.x, &false_value.x)?,
+            y: cond.select(&true_value.y, &false_value.y)?,
+        })
+    }
+}
+
+pub struct ParametersVar<P: SWCurveConfig, F: PrimeField>
+where
+    P::BaseField: PrimeField,
+{
+    pub generator: EmulatedPointVar<P, F>,
+    /// Window tables of `generator`, precomputed when the parameters are allocated as a constant.
+    pub generator_table: Option<FixedBaseTable<Projective<P>>>,
+    /// The `accumulator_offset`, derived once here rather than on every scalar multiplication.
+    pub offset: Affine<P>,
+}
+
+impl<P, F> Clone for ParametersVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn clone(&self) -> Self {
+        Self {
+            generator: self.generator.clone(),
+            generator_table: self.generator_table.clone(),
+            offset: self.offset,
+        }
+    }
+}
+
+impl<P, F> ParametersVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    /// Computes `bits * generator`, without any doublings when the generator is a constant.
+    pub fn scalar_mul_generator_le(&self, bits: &[Boolean<F>]) -> Result<EmulatedPointVar<P, F>, SynthesisError> {
+        self.generator.fixed_base_scalar_mul_le(self.generator_table.as_ref(), bits, &self.offset)
+    }
+}
+
+impl<P, F> AllocVar<Parameters<Projective<P>>, F> for ParametersVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn new_variable<T: Borrow<Parameters<Projective<P>>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let value = f().map(|p| p.borrow().generator);
+        let generator = EmulatedPointVar::new_variable(cs, || value, mode)?;
+
+        let generator_table = match (mode, value) {
+            (AllocationMode::Constant, Ok(g)) => Some(window_tables::<Projective<P>>(&g)),
+            _ => None,
+        };
+
+        Ok(Self {
+            generator,
+            generator_table,
+            offset: accumulator_offset::<P>(),
+        })
+    }
+}
+
+pub struct PlaintextVar<P: SWCurveConfig, F: PrimeField>
+where
+    P::BaseField: PrimeField,
+{
+    pub plaintext: EmulatedPointVar<P, F>,
+}
+
+impl<P, F> Clone for PlaintextVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn clone(&self) -> Self {
+        Self {
+            plaintext: self.plaintext.clone(),
+        }
+    }
+}
+
+impl<P, F> AllocVar<Affine<P>, F> for PlaintextVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn new_variable<T: Borrow<Affine<P>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let plaintext = EmulatedPointVar::new_variable(cs, f, mode)?;
+        Ok(Self { plaintext })
+    }
+}
+
+pub struct PublicKeyVar<P: SWCurveConfig, F: PrimeField>
+where
+    P::BaseField: PrimeField,
+{
+    pub pk: EmulatedPointVar<P, F>,
+    /// Window tables of `pk`, precomputed when the key is allocated as a constant.
+    pub table: Option<FixedBaseTable<Projective<P>>>,
+}
+
+impl<P, F> Clone for PublicKeyVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn clone(&self) -> Self {
+        Self {
+            pk: self.pk.clone(),
+            table: self.table.clone(),
+        }
+    }
+}
+
+impl<P, F> AllocVar<Affine<P>, F> for PublicKeyVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn new_variable<T: Borrow<Affine<P>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let value = f().map(|p| *p.borrow());
+        let pk = EmulatedPointVar::new_variable(cs, || value, mode)?;
+
+        let table = match (mode, value) {
+            (AllocationMode::Constant, Ok(pk)) => Some(window_tables::<Projective<P>>(&pk)),
+            _ => None,
+        };
+
+        Ok(Self { pk, table })
+    }
+}
+
+pub struct OutputVar<P: SWCurveConfig, F: PrimeField>
+where
+    P::BaseField: PrimeField,
+{
+    pub c1: EmulatedPointVar<P, F>,
+    pub c2: EmulatedPointVar<P, F>,
+}
+
+impl<P, F> Clone for OutputVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn clone(&self) -> Self {
+        Self {
+            c1: self.c1.clone(),
+            c2: self.c2.clone(),
+        }
+    }
+}
+
+impl<P, F> AllocVar<Ciphertext<Projective<P>>, F> for OutputVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn new_variable<T: Borrow<Ciphertext<Projective<P>>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let prep = f().map(|g| *g.borrow());
+        let c1 = EmulatedPointVar::new_variable(cs.clone(), || prep.map(|g| g.0), mode)?;
+        let c2 = EmulatedPointVar::new_variable(cs.clone(), || prep.map(|g| g.1), mode)?;
+        Ok(Self { c1, c2 })
+    }
+}
+
+impl<P, F> R1CSVar<F> for OutputVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    type Value = Ciphertext<Projective<P>>;
+
+    fn cs(&self) -> ConstraintSystemRef<F> {
+        self.c1.cs().or(self.c2.cs())
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(Ciphertext(self.c1.value()?, self.c2.value()?))
+    }
+}
+
+impl<P, F> EqGadget<F> for OutputVar<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    fn is_eq(&self, other: &Self) -> Result<Boolean<F>, SynthesisError> {
+        self.c1.is_eq(&other.c1)?.and(&self.c2.is_eq(&other.c2)?)
+    }
+}
+
+/// ElGamal over the short Weierstrass curve `P`, proven in a circuit over `F`.
+pub struct EmulatedElGamalEncGadget<P: SWCurveConfig, F: PrimeField> {
+    #[doc(hidden)]
+    _curve: PhantomData<*const P>,
+    _field: PhantomData<*const F>,
+}
+
+impl<P, F> AsymmetricEncryptionGadget<ElGamal<Projective<P>>, F> for EmulatedElGamalEncGadget<P, F>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+    F: PrimeField,
+{
+    type OutputVar = OutputVar<P, F>;
+    type ParametersVar = ParametersVar<P, F>;
+    type PlaintextVar = PlaintextVar<P, F>;
+    type PublicKeyVar = PublicKeyVar<P, F>;
+    type RandomnessVar = RandomnessVar<F>;
+
+    fn encrypt(
+        parameters: &Self::ParametersVar,
+        message: &Self::PlaintextVar,
+        randomness: &Self::RandomnessVar,
+        public_key: &Self::PublicKeyVar,
+    ) -> Result<Self::OutputVar, SynthesisError> {
+        let randomness = &randomness.0;
+
+        // compute s = randomness*pk
+        let s = public_key
+            .pk
+            .fixed_base_scalar_mul_le(public_key.table.as_ref(), randomness, &parameters.offset)?;
+
+        // compute c1 = randomness*generator
+        let c1 = parameters.scalar_mul_generator_le(randomness)?;
+
+        // compute c2 = m + s
+        let c2 = message.plaintext.add(&s)?;
+
+        Ok(Self::OutputVar { c1, c2 })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::{Fr, G1Affine, G1Projective};
+    use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+    use ark_bn254::Bn254;
+    use ark_crypto_primitives::snark::{CircuitSpecificSetupSNARK, SNARK};
+    use ark_ec::{AffineRepr, CurveConfig, CurveGroup};
+    use ark_ff::{MontFp, PrimeField};
+    use ark_groth16::Groth16;
+    use ark_r1cs_std::prelude::{AllocVar, Boolean, EqGadget};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, SynthesisError};
+    use ark_std::{test_rng, UniformRand};
+    use rand::{RngCore, SeedableRng};
+
+    use super::{accumulator_offset, ciphertext_public_inputs, point_public_inputs, EmulatedElGamalEncGadget, EmulatedPointVar};
+    use super::{OutputVar, ParametersVar, PlaintextVar, PublicKeyVar};
+    use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+    use crate::gadgets::public_encryptions::elgamal::constraints::RandomnessVar;
+    use crate::gadgets::public_encryptions::elgamal::{Ciphertext, ElGamal, Parameters, Randomness};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type MyEnc = ElGamal<G1Projective>;
+    type MyGadget = EmulatedElGamalEncGadget<ark_bn254::g1::Config, Fr>;
+
+    // proves that `ct` encrypts a witness point to `pk` under BN254 G1 ElGamal
+    struct EmulatedElGamalCircuit {
+        parameters: Parameters<G1Projective>,
+        pk: Option<G1Affine>,
+        ct: Option<Ciphertext<G1Projective>>,
+        msg: Option<G1Affine>,
+        r: Option<Randomness<G1Projective>>,
+    }
+
+    impl ConstraintSynthesizer<Fr> for EmulatedElGamalCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            let missing = SynthesisError::AssignmentMissing;
+            let parameters = ParametersVar::new_constant(cs.clone(), &self.parameters)?;
+            let pk = PublicKeyVar::new_input(cs.clone(), || self.pk.ok_or(missing))?;
+            let ct = OutputVar::new_input(cs.clone(), || self.ct.ok_or(missing))?;
+            let msg = PlaintextVar::new_witness(cs.clone(), || self.msg.ok_or(missing))?;
+            let r = RandomnessVar::new_witness(cs.clone(), || self.r.as_ref().ok_or(missing))?;
+
+            MyGadget::encrypt(&parameters, &msg, &r, &pk)?.enforce_equal(&ct)
+        }
+    }
+
+    // BabyJubJub in short Weierstrass form, a curve with cofactor 8 over BN254's scalar field
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    struct BabyJubJubConfig;
+
+    impl CurveConfig for BabyJubJubConfig {
+        type BaseField = Fr;
+        type ScalarField = ark_ed_on_bn254::Fr;
+
+        const COFACTOR: &'static [u64] = &[8];
+        const COFACTOR_INV: ark_ed_on_bn254::Fr =
+            MontFp!("2394026564107420727433200628387514462817212225638746351800188703329891451411");
+    }
+
+    impl SWCurveConfig for BabyJubJubConfig {
+        const COEFF_A: Fr = MontFp!("3915561033734670630843635270522714716872400990323396055797168613637673095919");
+        const COEFF_B: Fr = MontFp!("4217185138631398382466346491768379401896178114478749112717062407767665636606");
+        // the image of ark_ed_on_bn254's generator
+        const GENERATOR: Affine<Self> = Affine::new_unchecked(
+            MontFp!("4513000517330448244903653178865560289910339884906555605055646870021619219232"),
+            MontFp!("12354950672345577792670528317750261467336531611841695810091486319550864339243"),
+        );
+    }
+
+    #[test]
+    fn test_emulated_point_arithmetic() {
+        let rng = &mut test_rng();
+
+        let p = G1Projective::rand(rng).into_affine();
+        let q = G1Projective::rand(rng).into_affine();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        // an input, so that the window tables mix reduced and unreduced coordinates
+        let p_var = EmulatedPointVar::new_input(cs.clone(), || Ok(p)).unwrap();
+        let q_var = EmulatedPointVar::new_witness(cs.clone(), || Ok(q)).unwrap();
+
+        assert_eq!((p + q).into_affine(), p_var.add(&q_var).unwrap().value().unwrap());
+        assert_eq!((p + p).into_affine(), p_var.double().unwrap().value().unwrap());
+        let offset = accumulator_offset::<ark_bn254::g1::Config>();
+        for k in [0xb3u64, 0x24] {
+            let k_bits = (0..8)
+                .map(|i| Boolean::new_witness(cs.clone(), || Ok(k >> i & 1 == 1)).unwrap())
+                .collect::<Vec<_>>();
+            let product = p_var.scalar_mul_le(&k_bits, &offset).unwrap();
+            assert_eq!((p * Fr::from(k)).into_affine(), product.value().unwrap());
+        }
+        assert!(cs.is_satisfied().unwrap());
+
+        // adding a point to itself has no valid slope
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let p_var = EmulatedPointVar::<ark_bn254::g1::Config, Fr>::new_input(cs.clone(), || Ok(p)).unwrap();
+        p_var.add(&p_var).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // points off the curve, including the identity, are rejected
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        EmulatedPointVar::<ark_bn254::g1::Config, Fr>::new_witness(cs.clone(), || Ok(G1Affine::zero())).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_emulated_point_subgroup_check() {
+        type Point = Affine<BabyJubJubConfig>;
+        let rng = &mut test_rng();
+
+        let generator = BabyJubJubConfig::GENERATOR;
+        assert!(generator.is_on_curve() && generator.is_in_correct_subgroup_assuming_on_curve());
+
+        let cs = ConstraintSystem::<ark_bn254::Fq>::new_ref();
+        let p = (generator * ark_ed_on_bn254::Fr::rand(rng)).into_affine();
+        EmulatedPointVar::<BabyJubJubConfig, ark_bn254::Fq>::new_witness(cs.clone(), || Ok(p)).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // a point on the curve with a component of small order is rejected
+        let outside = (1u64..)
+            .filter_map(|x| Point::get_point_from_x_unchecked(Fr::from(x), false))
+            .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        let cs = ConstraintSystem::<ark_bn254::Fq>::new_ref();
+        EmulatedPointVar::<BabyJubJubConfig, ark_bn254::Fq>::new_witness(cs.clone(), || Ok(outside)).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_emulated_elgamal_gadget() {
+        let rng = &mut test_rng();
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg = G1Projective::rand(rng).into_affine();
+        // a 16-bit randomness keeps the emulated scalar multiplications affordable in debug builds;
+        // the gadget does not depend on the bit length beyond the loop count
+        let r = 0xa5c3u64;
+        let randomness = Randomness(Fr::from(r));
+        let primitive_result = MyEnc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+        let other = MyEnc::encrypt(&parameters, &pk, &msg, &Randomness::rand(rng)).unwrap();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let parameters_var = ParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+        let pk_var = PublicKeyVar::new_input(cs.clone(), || Ok(pk)).unwrap();
+        let expected_var = OutputVar::new_input(cs.clone(), || Ok(primitive_result)).unwrap();
+        let msg_var = PlaintextVar::new_witness(cs.clone(), || Ok(msg)).unwrap();
+        let randomness_var = RandomnessVar(
+            (0..16)
+                .map(|i| Boolean::new_witness(cs.clone(), || Ok(r >> i & 1 == 1)).unwrap())
+                .collect(),
+        );
+
+        let mut inputs = point_public_inputs::<_, Fr>(&pk);
+        inputs.extend(ciphertext_public_inputs::<_, Fr>(&primitive_result));
+        assert_eq!(inputs, cs.borrow().unwrap().instance_assignment[1..]);
+
+        let result_var = MyGadget::encrypt(&parameters_var, &msg_var, &randomness_var, &pk_var).unwrap();
+        assert_eq!(primitive_result, result_var.value().unwrap());
+
+        let other_var = OutputVar::new_witness(cs.clone(), || Ok(other)).unwrap();
+        assert!(!result_var.is_eq(&other_var).unwrap().value().unwrap());
+
+        result_var.enforce_equal(&expected_var).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_emulated_elgamal_gadget_full_randomness() {
+        let rng = &mut test_rng();
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg = G1Projective::rand(rng).into_affine();
+        let randomness = Randomness::rand(rng);
+        let primitive_result = MyEnc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+
+        // full-width canonical bits from the public constructor, checked for satisfiability only.
+        // A constant key takes the precomputed path like the generator, which keeps this within
+        // a debug build. An input key with these bits is left to the ignored Groth16 test: it
+        // needs over 5GB even in release builds. That is safe because the variable-base path
+        // repeats the same window step for every 3 bits, and that step is checked against an
+        // input point by `test_emulated_point_arithmetic` and an input key by
+        // `test_emulated_elgamal_gadget`
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let parameters_var = ParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+        let pk_var = PublicKeyVar::new_constant(cs.clone(), pk).unwrap();
+        let expected_var = OutputVar::new_input(cs.clone(), || Ok(primitive_result)).unwrap();
+        let msg_var = PlaintextVar::new_witness(cs.clone(), || Ok(msg)).unwrap();
+        let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+        assert_eq!(<Fr as PrimeField>::MODULUS_BIT_SIZE as usize, randomness_var.0.len());
+
+        let result_var = MyGadget::encrypt(&parameters_var, &msg_var, &randomness_var, &pk_var).unwrap();
+        assert_eq!(primitive_result, result_var.value().unwrap());
+        result_var.enforce_equal(&expected_var).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    // about 1.6M constraints, which takes several GB and minutes even in release builds;
+    // run with `cargo test --release -- --ignored`
+    #[test]
+    #[ignore]
+    fn test_groth16_emulated_elgamal_full_randomness() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg = G1Projective::rand(rng).into_affine();
+        let randomness = Randomness::rand(rng);
+        let ct = MyEnc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+
+        // the randomness goes through the public constructor, as full-width canonical bits
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+        assert_eq!(<Fr as PrimeField>::MODULUS_BIT_SIZE as usize, randomness_var.0.len());
+
+        let setup_circuit = EmulatedElGamalCircuit {
+            parameters: parameters.clone(),
+            pk: None,
+            ct: None,
+            msg: None,
+            r: None,
+        };
+        let (proving_key, vk) = Groth16::<Bn254>::setup(setup_circuit, rng).unwrap();
+        let pvk = Groth16::<Bn254>::process_vk(&vk).unwrap();
+
+        let circuit = EmulatedElGamalCircuit {
+            parameters,
+            pk: Some(pk),
+            ct: Some(ct),
+            msg: Some(msg),
+            r: Some(randomness),
+        };
+        let proof = Groth16::<Bn254>::prove(&proving_key, circuit, rng).unwrap();
+
+        let mut inputs = point_public_inputs::<_, Fr>(&pk);
+        inputs.extend(ciphertext_public_inputs::<_, Fr>(&ct));
+        assert!(Groth16::<Bn254>::verify_with_processed_vk(&pvk, &inputs, &proof).unwrap());
+
+        let other_pk = G1Projective::rand(rng).into_affine();
+        let mut other_inputs = point_public_inputs::<_, Fr>(&other_pk);
+        other_inputs.extend(ciphertext_public_inputs::<_, Fr>(&ct));
+        assert!(!Groth16::<Bn254>::verify_with_processed_vk(&pvk, &other_inputs, &proof).unwrap());
+    }
+}
@@ -0,0 +1,163 @@
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use ark_std::UniformRand;
+use rand::Rng;
+
+use super::{Ciphertext, ElGamal, Parameters, Plaintext, PublicKey, Randomness, SecretKey};
+use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+// Layout of the compressed coordinate: [counter (2 bytes) | length (1 byte) | payload | zeros].
+const HEADER_LEN: usize = 3;
+
+/// Number of message bytes carried by a single plaintext point.
+pub fn chunk_size<C>() -> usize
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+{
+    (C::BaseField::MODULUS_BIT_SIZE as usize - 1) / 8 - HEADER_LEN
+}
+
+/// Encodes one chunk with try-and-increment over the counter, keeping only points of the
+/// prime-order subgroup so that the result is a valid `Plaintext`.
+fn encode_chunk<C>(chunk: &[u8]) -> Result<Plaintext<C>, ark_crypto_primitives::Error>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+{
+    let len = C::Affine::generator().compressed_size();
+    let mut bytes = vec![0u8; len];
+    bytes[2] = chunk.len() as u8;
+    bytes[HEADER_LEN..HEADER_LEN + chunk.len()].copy_from_slice(chunk);
+
+    for counter in 0..=u16::MAX {
+        bytes[..2].copy_from_slice(&counter.to_le_bytes());
+        if let Some(point) = C::Affine::from_random_bytes(&bytes) {
+            if point.mul_bigint(C::ScalarField::MODULUS).is_zero() {
+                return Ok(point);
+            }
+        }
+    }
+    Err("no curve point found for message chunk".into())
+}
+
+fn decode_chunk<C>(point: &Plaintext<C>) -> Result<Vec<u8>, ark_crypto_primitives::Error>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+{
+    let capacity = chunk_size::<C>();
+
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes)?;
+
+    let chunk_len = bytes[2] as usize;
+    let padding = &bytes[HEADER_LEN..HEADER_LEN + capacity];
+    if chunk_len > capacity || padding[chunk_len..].iter().any(|b| *b != 0) {
+        return Err("point is not a valid message encoding".into());
+    }
+    Ok(padding[..chunk_len].to_vec())
+}
+
+/// Splits `message` into `chunk_size` pieces and maps each to a curve point.
+/// An empty message is encoded as a single empty chunk.
+pub fn encode<C>(message: &[u8]) -> Result<Vec<Plaintext<C>>, ark_crypto_primitives::Error>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+{
+    if message.is_empty() {
+        return Ok(vec![encode_chunk::<C>(&[])?]);
+    }
+    message.chunks(chunk_size::<C>()).map(encode_chunk::<C>).collect()
+}
+
+pub fn decode<C>(points: &[Plaintext<C>]) -> Result<Vec<u8>, ark_crypto_primitives::Error>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+{
+    let mut message = Vec::new();
+    for point in points {
+        message.extend(decode_chunk::<C>(point)?);
+    }
+    Ok(message)
+}
+
+/// Encrypts an arbitrary byte string as one ciphertext per encoded chunk.
+pub fn encrypt_bytes<C, R>(
+    pp: &Parameters<C>,
+    pk: &PublicKey<C>,
+    message: &[u8],
+    rng: &mut R,
+) -> Result<Vec<Ciphertext<C>>, ark_crypto_primitives::Error>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+    R: Rng,
+{
+    encode::<C>(message)?
+        .iter()
+        .map(|m| ElGamal::<C>::encrypt(pp, pk, m, &Randomness::rand(rng)))
+        .collect()
+}
+
+pub fn decrypt_bytes<C>(
+    pp: &Parameters<C>,
+    sk: &SecretKey<C>,
+    ciphertexts: &[Ciphertext<C>],
+) -> Result<Vec<u8>, ark_crypto_primitives::Error>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+{
+    let points = ciphertexts
+        .iter()
+        .map(|ct| ElGamal::<C>::decrypt(pp, sk, ct))
+        .collect::<Result<Vec<_>, _>>()?;
+    decode::<C>(&points)
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{chunk_size, decode, decrypt_bytes, encode, encrypt_bytes};
+    use crate::gadgets::public_encryptions::elgamal::ElGamal;
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+
+    #[test]
+    fn test_encoding_round_trip() {
+        assert_eq!(28, chunk_size::<C>());
+
+        for len in [0usize, 1, 27, 28, 29, 56, 100] {
+            let message = (0..len).map(|i| (i * 7 + 3) as u8).collect::<Vec<_>>();
+            let points = encode::<C>(&message).unwrap();
+            assert_eq!(points.len(), len.div_ceil(28).max(1));
+            assert_eq!(message, decode::<C>(&points).unwrap());
+        }
+
+        // all-ones bytes still fit below the field modulus
+        let message = vec![0xffu8; 28];
+        assert_eq!(message, decode::<C>(&encode::<C>(&message).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_encrypt_bytes() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let message = b"account-id:0x5f3a9c2e7b1d4e6f8a0b2c4d6e8f0a1b2c3d4e5f";
+        let ciphertexts = encrypt_bytes(&parameter, &pk, message, rng).unwrap();
+        assert_eq!(2, ciphertexts.len());
+        assert_eq!(message.to_vec(), decrypt_bytes(&parameter, &sk, &ciphertexts).unwrap());
+
+        // a random point is (almost surely) not a message encoding
+        assert!(decode::<C>(&[C::rand(rng).into()]).is_err());
+    }
+}
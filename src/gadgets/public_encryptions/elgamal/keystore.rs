@@ -0,0 +1,273 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, AeadInPlace, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::{CryptoRng, Rng};
+use zeroize::Zeroizing;
+
+use super::SecretKey;
+
+// File layout (all integers little-endian):
+//
+//   magic "AKSK" | version u8 | m_cost u32 | t_cost u32 | p_cost u32 | salt [16] | nonce [12]
+//   | ChaCha20-Poly1305(key = Argon2id(password, salt), aad = header, compressed SecretKey)
+const MAGIC: &[u8; 4] = b"AKSK";
+pub const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = 4 + 1 + 3 * 4 + SALT_LEN + NONCE_LEN;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeystoreError {
+    InvalidFormat,
+    UnsupportedVersion(u8),
+    InvalidKdfParams,
+    /// Authentication failed: the password is wrong or the file was modified.
+    DecryptionFailed,
+}
+
+impl fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeystoreError::InvalidFormat => write!(f, "not a keystore file"),
+            KeystoreError::UnsupportedVersion(v) => write!(f, "unsupported keystore version {}", v),
+            KeystoreError::InvalidKdfParams => write!(f, "invalid key derivation parameters"),
+            KeystoreError::DecryptionFailed => write!(f, "wrong password or corrupted keystore"),
+        }
+    }
+}
+
+impl std::error::Error for KeystoreError {}
+
+/// Argon2id cost parameters stored alongside each keystore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory size in KiB.
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost: 19 * 1024,
+            t_cost: 2,
+            p_cost: 1,
+        }
+    }
+}
+
+/// The largest costs `seal` writes and `open` accepts, four times the defaults. The header is
+/// only authenticated after the key is derived, so `open` checks it against these first;
+/// otherwise a crafted file could demand gigabytes of memory or hours of hashing.
+pub const MAX_KDF_PARAMS: KdfParams = KdfParams {
+    m_cost: 4 * 19 * 1024,
+    t_cost: 4 * 2,
+    p_cost: 4,
+};
+
+impl KdfParams {
+    fn within_limits(&self) -> bool {
+        self.m_cost <= MAX_KDF_PARAMS.m_cost && self.t_cost <= MAX_KDF_PARAMS.t_cost && self.p_cost <= MAX_KDF_PARAMS.p_cost
+    }
+}
+
+fn derive_key(
+    password: &[u8],
+    salt: &[u8],
+    params: &KdfParams,
+) -> Result<Zeroizing<[u8; 32]>, KeystoreError> {
+    if !params.within_limits() {
+        return Err(KeystoreError::InvalidKdfParams);
+    }
+    let params = Params::new(params.m_cost, params.t_cost, params.p_cost, Some(32))
+        .map_err(|_| KeystoreError::InvalidKdfParams)?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password, salt, key.as_mut())
+        .map_err(|_| KeystoreError::InvalidKdfParams)?;
+    Ok(key)
+}
+
+/// Encrypts `sk` under a key derived from `password`.
+pub fn seal<C: CurveGroup, R: Rng + CryptoRng>(
+    sk: &SecretKey<C>,
+    password: &[u8],
+    params: &KdfParams,
+    rng: &mut R,
+) -> Result<Vec<u8>, ark_crypto_primitives::Error> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut nonce);
+
+    let mut out = Vec::with_capacity(HEADER_LEN + sk.compressed_size() + 16);
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&params.m_cost.to_le_bytes());
+    out.extend_from_slice(&params.t_cost.to_le_bytes());
+    out.extend_from_slice(&params.p_cost.to_le_bytes());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+
+    // sized up front so that no reallocation leaves an unzeroized copy behind
+    let mut plaintext = Zeroizing::new(Vec::with_capacity(sk.compressed_size()));
+    sk.serialize_compressed(&mut *plaintext)?;
+
+    let key = derive_key(password, &salt, params)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key.as_ref()));
+    let sealed = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: &plaintext, aad: &out })
+        .map_err(|_| KeystoreError::DecryptionFailed)?;
+    out.extend_from_slice(&sealed);
+
+    Ok(out)
+}
+
+/// Decrypts a keystore produced by `seal`.
+pub fn open<C: CurveGroup>(
+    bytes: &[u8],
+    password: &[u8],
+) -> Result<SecretKey<C>, ark_crypto_primitives::Error> {
+    if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+        return Err(KeystoreError::InvalidFormat.into());
+    }
+    if bytes[4] != VERSION {
+        return Err(KeystoreError::UnsupportedVersion(bytes[4]).into());
+    }
+
+    let read_u32 = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+    let params = KdfParams {
+        m_cost: read_u32(5),
+        t_cost: read_u32(9),
+        p_cost: read_u32(13),
+    };
+    let (header, sealed) = bytes.split_at(HEADER_LEN);
+    let salt = &header[17..17 + SALT_LEN];
+    let nonce = &header[17 + SALT_LEN..];
+
+    let key = derive_key(password, salt, &params)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key.as_ref()));
+    // decrypted in place in a buffer that never reallocates, so every copy is zeroized
+    let mut plaintext = Zeroizing::new(Vec::with_capacity(sealed.len()));
+    plaintext.extend_from_slice(sealed);
+    cipher
+        .decrypt_in_place(Nonce::from_slice(nonce), header, &mut *plaintext)
+        .map_err(|_| KeystoreError::DecryptionFailed)?;
+
+    SecretKey::deserialize_compressed(&plaintext[..]).map_err(|_| KeystoreError::InvalidFormat.into())
+}
+
+pub fn write_to_file<C: CurveGroup, R: Rng + CryptoRng>(
+    path: impl AsRef<Path>,
+    sk: &SecretKey<C>,
+    password: &[u8],
+    params: &KdfParams,
+    rng: &mut R,
+) -> Result<(), ark_crypto_primitives::Error> {
+    fs::write(path, seal(sk, password, params, rng)?)?;
+    Ok(())
+}
+
+pub fn read_from_file<C: CurveGroup>(
+    path: impl AsRef<Path>,
+    password: &[u8],
+) -> Result<SecretKey<C>, ark_crypto_primitives::Error> {
+    open(&fs::read(path)?, password)
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::{test_rng, UniformRand};
+    use rand::{RngCore, SeedableRng};
+
+    use super::{open, read_from_file, seal, write_to_file, KdfParams, KeystoreError, MAX_KDF_PARAMS};
+    use crate::gadgets::public_encryptions::elgamal::{ElGamal, Randomness, SecretKey};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+
+    // cheap parameters keep the tests fast
+    const PARAMS: KdfParams = KdfParams { m_cost: 64, t_cost: 1, p_cost: 1 };
+
+    fn error_of(result: Result<SecretKey<C>, ark_crypto_primitives::Error>) -> KeystoreError {
+        result.unwrap_err().downcast_ref::<KeystoreError>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_keystore_round_trip() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let sealed = seal(&sk, b"correct horse", &PARAMS, rng).unwrap();
+        let restored: SecretKey<C> = open(&sealed, b"correct horse").unwrap();
+        assert_eq!(sk.0, restored.0);
+
+        let msg = C::rand(rng).into();
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &Randomness::rand(rng)).unwrap();
+        assert_eq!(msg, ElGamal::<C>::decrypt(&parameter, &restored, &cipher).unwrap());
+
+        let path = std::env::temp_dir().join(format!("elgamal-keystore-{}.bin", std::process::id()));
+        write_to_file(&path, &sk, b"correct horse", &PARAMS, rng).unwrap();
+        let restored: SecretKey<C> = read_from_file(&path, b"correct horse").unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(sk.0, restored.0);
+    }
+
+    #[test]
+    fn test_keystore_rejects_wrong_password_and_tampering() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (_, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+        let sealed = seal(&sk, b"correct horse", &PARAMS, rng).unwrap();
+
+        assert_eq!(KeystoreError::DecryptionFailed, error_of(open(&sealed, b"battery staple")));
+
+        // the header is authenticated, so changing the KDF parameters is detected
+        let mut tampered = sealed.clone();
+        tampered[5] ^= 1;
+        assert_eq!(KeystoreError::DecryptionFailed, error_of(open(&tampered, b"correct horse")));
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(KeystoreError::DecryptionFailed, error_of(open(&tampered, b"correct horse")));
+
+        let mut future = sealed.clone();
+        future[4] = 2;
+        assert_eq!(KeystoreError::UnsupportedVersion(2), error_of(open(&future, b"correct horse")));
+
+        assert_eq!(KeystoreError::InvalidFormat, error_of(open(&sealed[..10], b"correct horse")));
+
+        // oversized costs are refused before any key derivation runs
+        let mut expensive = sealed.clone();
+        expensive[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(KeystoreError::InvalidKdfParams, error_of(open(&expensive, b"correct horse")));
+
+        let mut expensive = sealed.clone();
+        expensive[9..13].copy_from_slice(&(MAX_KDF_PARAMS.t_cost + 1).to_le_bytes());
+        assert_eq!(KeystoreError::InvalidKdfParams, error_of(open(&expensive, b"correct horse")));
+
+        let too_costly = KdfParams { p_cost: MAX_KDF_PARAMS.p_cost + 1, ..PARAMS };
+        assert!(seal(&sk, b"correct horse", &too_costly, rng).is_err());
+    }
+
+    #[test]
+    fn test_secret_debug_is_redacted() {
+        let rng = &mut test_rng();
+
+        let sk = SecretKey::<C>(<C as ark_ec::Group>::ScalarField::from(123456789u64));
+        let r = Randomness::<C>::rand(rng);
+
+        assert_eq!("SecretKey(<redacted>)", format!("{:?}", sk));
+        assert_eq!("Randomness(<redacted>)", format!("{:?}", r));
+    }
+}
@@ -1,31 +1,144 @@
+use std::fmt;
 use std::marker::PhantomData;
-use std::ops::Mul;
+use std::ops::{Add, Mul, Neg, Sub};
 
-use ark_ec::CurveGroup;
-use ark_ff::PrimeField;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField, ToConstraintField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::UniformRand;
 use rand::Rng;
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use super::AsymmetricEncryptionScheme;
 
 pub mod constraints;
+pub mod dleq;
+pub mod emulated;
+pub mod encoding;
+pub mod keystore;
+pub mod multi;
+pub mod threshold;
 
 pub struct ElGamal<C: CurveGroup> {
     _group: PhantomData<C>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Parameters<C: CurveGroup> {
     pub generator: C::Affine,
 }
 
+/// Domain separation string for the default transparent parameters.
+pub const DEFAULT_DOMAIN: &[u8] = b"arkworks-study/elgamal/generator/v1";
+
+/// Deterministically maps `(domain, message)` to a point of the prime-order subgroup.
+///
+/// Try-and-increment: SHA-256 output is interpreted as a compressed point until one lies on
+/// the curve, then the cofactor is cleared. Nobody learns the discrete log of the result.
+pub fn hash_to_curve<C: CurveGroup>(domain: &[u8], message: &[u8]) -> C::Affine {
+    let len = C::Affine::generator().compressed_size();
+
+    for counter in 0u64.. {
+        let mut bytes = Vec::with_capacity(len + 32);
+        for block in 0u64.. {
+            if bytes.len() >= len {
+                break;
+            }
+            let mut hasher = Sha256::new();
+            hasher.update((domain.len() as u64).to_le_bytes());
+            hasher.update(domain);
+            hasher.update((message.len() as u64).to_le_bytes());
+            hasher.update(message);
+            hasher.update(counter.to_le_bytes());
+            hasher.update(block.to_le_bytes());
+            bytes.extend_from_slice(&hasher.finalize());
+        }
+
+        if let Some(point) = C::Affine::from_random_bytes(&bytes[..len]) {
+            let point = point.clear_cofactor();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+    }
+    unreachable!()
+}
+
+impl<C: CurveGroup> Parameters<C> {
+    /// Parameters whose generator is `hash_to_curve(domain, "generator")`.
+    pub fn derive(domain: &[u8]) -> Self {
+        Self {
+            generator: hash_to_curve::<C>(domain, b"generator"),
+        }
+    }
+
+    /// Checks that these parameters were produced by `Parameters::derive(domain)`.
+    pub fn verify(&self, domain: &[u8]) -> bool {
+        *self == Self::derive(domain)
+    }
+}
+
+impl<C, F> ToConstraintField<F> for Parameters<C>
+where
+    C: CurveGroup,
+    F: Field,
+    C::Affine: ToConstraintField<F>,
+{
+    fn to_field_elements(&self) -> Option<Vec<F>> {
+        self.generator.to_field_elements()
+    }
+}
+
+/// The secret key times the generator.
 pub type PublicKey<C> = <C as CurveGroup>::Affine;
 
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct SecretKey<C: CurveGroup>(pub C::ScalarField);
 
+impl<C: CurveGroup> fmt::Debug for SecretKey<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(<redacted>)")
+    }
+}
+
+impl<C: CurveGroup> Zeroize for SecretKey<C> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<C: CurveGroup> Drop for SecretKey<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<C: CurveGroup> ZeroizeOnDrop for SecretKey<C> {}
 
-#[derive(Clone)]
+
+#[derive(Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Randomness<C: CurveGroup>(pub C::ScalarField);
+
+impl<C: CurveGroup> fmt::Debug for Randomness<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Randomness(<redacted>)")
+    }
+}
+
+impl<C: CurveGroup> Zeroize for Randomness<C> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<C: CurveGroup> Drop for Randomness<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<C: CurveGroup> ZeroizeOnDrop for Randomness<C> {}
 impl<C: CurveGroup> UniformRand for Randomness<C> {
     fn rand<R: Rng + ?Sized> (rng: &mut R) -> Self {
         Randomness(C::ScalarField::rand(rng))
@@ -34,7 +147,70 @@ impl<C: CurveGroup> UniformRand for Randomness<C> {
 
 pub type Plaintext<C> = <C as CurveGroup>::Affine;
 
-pub type Ciphertext<C> = (<C as CurveGroup>::Affine, <C as CurveGroup>::Affine);
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Ciphertext<C: CurveGroup>(pub C::Affine, pub C::Affine);
+
+impl<C: CurveGroup> Ciphertext<C> {
+    /// Adds an encryption of zero under `pk`, giving an unlinkable ciphertext of the same plaintext.
+    pub fn rerandomize(&self, pp: &Parameters<C>, pk: &PublicKey<C>, r: &Randomness<C>) -> Self {
+        let c1 = (self.0 + pp.generator.mul(r.0)).into();
+        let c2 = (self.1 + pk.mul(r.0)).into();
+        Ciphertext(c1, c2)
+    }
+}
+
+impl<C, F> ToConstraintField<F> for Ciphertext<C>
+where
+    C: CurveGroup,
+    F: Field,
+    C::Affine: ToConstraintField<F>,
+{
+    /// The elements of `c1` followed by those of `c2`.
+    fn to_field_elements(&self) -> Option<Vec<F>> {
+        let mut elements = self.0.to_field_elements()?;
+        elements.extend(self.1.to_field_elements()?);
+        Some(elements)
+    }
+}
+
+impl<C: CurveGroup> Add for Ciphertext<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Ciphertext((self.0 + other.0).into(), (self.1 + other.1).into())
+    }
+}
+
+impl<C: CurveGroup> Sub for Ciphertext<C> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<C: CurveGroup> Neg for Ciphertext<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Ciphertext((-self.0.into_group()).into(), (-self.1.into_group()).into())
+    }
+}
+
+impl<C: CurveGroup> Mul<C::ScalarField> for Ciphertext<C> {
+    type Output = Self;
+
+    fn mul(self, scalar: C::ScalarField) -> Self {
+        Ciphertext(self.0.mul(scalar).into(), self.1.mul(scalar).into())
+    }
+}
+
+impl<C: CurveGroup> ElGamal<C> {
+    /// Reproducible setup: every party deriving from the same domain gets the same generator.
+    pub fn setup_transparent(domain: &[u8]) -> Parameters<C> {
+        Parameters::derive(domain)
+    }
+}
 
 impl<C: CurveGroup> AsymmetricEncryptionScheme for ElGamal<C>
 where
@@ -70,7 +246,7 @@ where
         let c1 = pp.generator.mul(r.0).into();
         let c2 = (*message + s).into();
 
-        Ok((c1, c2))
+        Ok(Ciphertext(c1, c2))
     }
 
     fn decrypt(
@@ -93,9 +269,10 @@ where
 #[cfg(test)]
 mod test {
     use ark_ec::CurveGroup;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
     use ark_std::{test_rng, UniformRand};
 
-    use super::{ElGamal, Randomness};
+    use super::{Ciphertext, ElGamal, Parameters, Randomness, SecretKey, DEFAULT_DOMAIN};
     
     use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
 
@@ -116,4 +293,147 @@ mod test {
 
         assert_eq!(msg, check_msg);
     }
+
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: CanonicalSerialize + CanonicalDeserialize,
+    {
+        let mut compressed = Vec::new();
+        value.serialize_compressed(&mut compressed).unwrap();
+        assert_eq!(compressed.len(), value.compressed_size());
+
+        let mut uncompressed = Vec::new();
+        value.serialize_uncompressed(&mut uncompressed).unwrap();
+        assert_eq!(uncompressed.len(), value.uncompressed_size());
+
+        let from_uncompressed = T::deserialize_uncompressed(&uncompressed[..]).unwrap();
+        let mut reencoded = Vec::new();
+        from_uncompressed.serialize_compressed(&mut reencoded).unwrap();
+        assert_eq!(compressed, reencoded);
+
+        T::deserialize_compressed(&compressed[..]).unwrap()
+    }
+
+    #[test]
+    fn test_elgamal_transparent_setup() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup_transparent(DEFAULT_DOMAIN);
+        assert_eq!(parameter, ElGamal::<C>::setup_transparent(DEFAULT_DOMAIN));
+        assert!(parameter.verify(DEFAULT_DOMAIN));
+        assert!(parameter.generator.is_in_correct_subgroup_assuming_on_curve());
+        assert!(!parameter.generator.is_zero());
+
+        // pinned so that a change of the derivation is caught
+        let mut bytes = Vec::new();
+        parameter.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(
+            "32642012771694c66c6a28f8cbd9fdd019c7583ef50b460865c0c4d771a8c32f",
+            bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>(),
+        );
+
+        let other = ElGamal::<C>::setup_transparent(b"another domain");
+        assert_ne!(parameter, other);
+        assert!(!other.verify(DEFAULT_DOMAIN));
+        assert!(!ElGamal::<C>::setup(rng).unwrap().verify(DEFAULT_DOMAIN));
+
+        let (pk, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+        let msg: <C as CurveGroup>::Affine = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+        assert_eq!(msg, ElGamal::<C>::decrypt(&parameter, &sk, &cipher).unwrap());
+    }
+
+    #[test]
+    fn test_elgamal_homomorphism() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let m1 = C::rand(rng);
+        let m2 = C::rand(rng);
+        let k = <C as ark_ec::Group>::ScalarField::rand(rng);
+
+        let ct1 = ElGamal::<C>::encrypt(&parameter, &pk, &m1.into(), &Randomness::rand(rng)).unwrap();
+        let ct2 = ElGamal::<C>::encrypt(&parameter, &pk, &m2.into(), &Randomness::rand(rng)).unwrap();
+
+        let sum = ElGamal::<C>::decrypt(&parameter, &sk, &(ct1 + ct2)).unwrap();
+        assert_eq!(sum, (m1 + m2).into_affine());
+
+        let difference = ElGamal::<C>::decrypt(&parameter, &sk, &(ct1 - ct2)).unwrap();
+        assert_eq!(difference, (m1 - m2).into_affine());
+
+        let scaled = ElGamal::<C>::decrypt(&parameter, &sk, &(ct1 * k)).unwrap();
+        assert_eq!(scaled, (m1 * k).into_affine());
+    }
+
+    #[test]
+    fn test_elgamal_rerandomize() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let msg: <C as CurveGroup>::Affine = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+
+        let r_prime: Randomness<C> = Randomness::rand(rng);
+        let rerandomized = cipher.rerandomize(&parameter, &pk, &r_prime);
+        assert_ne!(cipher, rerandomized);
+        assert_eq!(msg, ElGamal::<C>::decrypt(&parameter, &sk, &rerandomized).unwrap());
+
+        // re-randomizing with r' is the same as encrypting with r + r'
+        let combined = Randomness(r.0 + r_prime.0);
+        assert_eq!(rerandomized, ElGamal::<C>::encrypt(&parameter, &pk, &msg, &combined).unwrap());
+    }
+
+    #[test]
+    fn test_elgamal_serialization() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+        let msg: <C as CurveGroup>::Affine = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+
+        assert_eq!(parameter, round_trip(&parameter));
+        assert_eq!(pk, round_trip(&pk));
+        assert_eq!(sk.0, round_trip(&sk).0);
+        assert_eq!(r, round_trip(&r));
+        assert_eq!(msg, round_trip(&msg));
+        assert_eq!(cipher, round_trip(&cipher));
+
+        // keys and ciphertexts restored from bytes remain usable
+        let sk: SecretKey<C> = round_trip(&sk);
+        let cipher: Ciphertext<C> = round_trip(&cipher);
+        let parameter: Parameters<C> = round_trip(&parameter);
+        assert_eq!(msg, ElGamal::<C>::decrypt(&parameter, &sk, &cipher).unwrap());
+    }
+
+    #[test]
+    fn test_elgamal_deserialization_validates() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, _) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+        let msg: <C as CurveGroup>::Affine = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+
+        let mut bytes = Vec::new();
+        cipher.serialize_uncompressed(&mut bytes).unwrap();
+
+        // perturb the y-coordinate of c1 so that it no longer lies on the curve
+        let coordinate_len = bytes.len() / 4;
+        bytes[coordinate_len] ^= 1;
+
+        assert!(Ciphertext::<C>::deserialize_uncompressed(&bytes[..]).is_err());
+        assert!(Ciphertext::<C>::deserialize_with_mode(&bytes[..], Compress::No, Validate::No).is_ok());
+
+        // truncated input is rejected
+        assert!(Parameters::<C>::deserialize_compressed(&bytes[..4]).is_err());
+    }
 }
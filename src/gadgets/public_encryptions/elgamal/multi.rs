@@ -0,0 +1,76 @@
+use std::ops::Mul;
+
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use super::{Ciphertext, ElGamal, Parameters, Plaintext, PublicKey, Randomness};
+
+/// One `c1 = r*G` shared by every recipient and one `c2_i = m + r*pk_i` per public key.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultiCiphertext<C: CurveGroup> {
+    pub c1: C::Affine,
+    pub c2: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> MultiCiphertext<C> {
+    /// The ordinary ElGamal ciphertext addressed to the `index`-th public key.
+    pub fn for_recipient(&self, index: usize) -> Option<Ciphertext<C>> {
+        self.c2.get(index).map(|c2| Ciphertext(self.c1, *c2))
+    }
+}
+
+impl<C: CurveGroup> ElGamal<C> {
+    /// Encrypts `message` to every key in `pks` with the same randomness.
+    /// The keys must be distinct, otherwise recipients share ciphertext components.
+    pub fn encrypt_multi(
+        pp: &Parameters<C>,
+        pks: &[PublicKey<C>],
+        message: &Plaintext<C>,
+        r: &Randomness<C>,
+    ) -> Result<MultiCiphertext<C>, ark_crypto_primitives::Error> {
+        if pks.is_empty() {
+            return Err("multi-recipient encryption needs at least one public key".into());
+        }
+
+        let c1 = pp.generator.mul(r.0).into();
+        let c2 = C::normalize_batch(
+            &pks.iter().map(|pk| pk.mul(r.0) + message).collect::<Vec<_>>(),
+        );
+
+        Ok(MultiCiphertext { c1, c2 })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::gadgets::public_encryptions::elgamal::{ElGamal, Randomness};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+
+    #[test]
+    fn test_multi_recipient_encryption() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let keys = (0..3)
+            .map(|_| ElGamal::<C>::keygen(&parameter, rng).unwrap())
+            .collect::<Vec<_>>();
+        let pks = keys.iter().map(|(pk, _)| *pk).collect::<Vec<_>>();
+
+        let msg = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt_multi(&parameter, &pks, &msg, &r).unwrap();
+
+        for (i, (pk, sk)) in keys.iter().enumerate() {
+            let single = cipher.for_recipient(i).unwrap();
+            assert_eq!(single, ElGamal::<C>::encrypt(&parameter, pk, &msg, &r).unwrap());
+            assert_eq!(msg, ElGamal::<C>::decrypt(&parameter, sk, &single).unwrap());
+        }
+        assert!(cipher.for_recipient(3).is_none());
+
+        assert!(ElGamal::<C>::encrypt_multi(&parameter, &[], &msg, &r).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Mul;
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use rand::Rng;
+use zeroize::Zeroize;
+
+use super::dleq::{prove_dleq, verify_dleq, DleqProof};
+use super::{Ciphertext, Parameters, Plaintext, PublicKey, SecretKey};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThresholdError {
+    InvalidThreshold { threshold: usize, parties: usize },
+    InvalidIndex(u64),
+    DuplicateIndex(u64),
+    InvalidPartialDecryption(u64),
+    NotEnoughShares { threshold: usize, received: usize },
+}
+
+impl fmt::Display for ThresholdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThresholdError::InvalidThreshold { threshold, parties } => {
+                write!(f, "invalid {}-of-{} threshold", threshold, parties)
+            }
+            ThresholdError::InvalidIndex(i) => write!(f, "share index {} is not valid", i),
+            ThresholdError::DuplicateIndex(i) => write!(f, "share index {} appears more than once", i),
+            ThresholdError::InvalidPartialDecryption(i) => {
+                write!(f, "partial decryption {} does not match its verification key", i)
+            }
+            ThresholdError::NotEnoughShares { threshold, received } => {
+                write!(f, "need {} partial decryptions, received {}", threshold, received)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThresholdError {}
+
+/// Evaluation `f(index)` of the dealer's polynomial, where `f(0)` is the secret key.
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SecretKeyShare<C: CurveGroup> {
+    pub index: u64,
+    pub share: C::ScalarField,
+}
+
+impl<C: CurveGroup> fmt::Debug for SecretKeyShare<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKeyShare {{ index: {}, share: <redacted> }}", self.index)
+    }
+}
+
+impl<C: CurveGroup> Drop for SecretKeyShare<C> {
+    fn drop(&mut self) {
+        self.share.zeroize();
+    }
+}
+
+/// `share * G` for share `index`, published by the dealer so partial decryptions can be checked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerificationKey<C: CurveGroup> {
+    pub index: u64,
+    pub value: C::Affine,
+}
+
+/// `share * c1` computed by the holder of share `index`, with a proof that
+/// `log_G(vk) == log_c1(value)` for the share's verification key `vk`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PartialDecryption<C: CurveGroup> {
+    pub index: u64,
+    pub value: C::Affine,
+    pub proof: DleqProof<C>,
+}
+
+/// The shares handed to each party, and the verification keys published for them.
+pub type Shares<C> = (Vec<SecretKeyShare<C>>, Vec<VerificationKey<C>>);
+
+/// Splits `sk` into `parties` shares such that any `threshold` of them can decrypt,
+/// together with the verification key of every share.
+pub fn share_secret_key<C: CurveGroup, R: Rng>(
+    pp: &Parameters<C>,
+    sk: &SecretKey<C>,
+    threshold: usize,
+    parties: usize,
+    rng: &mut R,
+) -> Result<Shares<C>, ark_crypto_primitives::Error> {
+    if threshold == 0 || threshold > parties {
+        return Err(ThresholdError::InvalidThreshold { threshold, parties }.into());
+    }
+
+    let mut coeffs = vec![sk.0];
+    coeffs.extend((1..threshold).map(|_| C::ScalarField::rand(rng)));
+    let mut polynomial = DensePolynomial::from_coefficients_vec(coeffs);
+
+    let shares = (1..=parties as u64)
+        .map(|index| SecretKeyShare {
+            index,
+            share: polynomial.evaluate(&C::ScalarField::from(index)),
+        })
+        .collect::<Vec<_>>();
+    let verification_keys = shares
+        .iter()
+        .map(|share: &SecretKeyShare<C>| VerificationKey {
+            index: share.index,
+            value: pp.generator.mul(share.share).into_affine(),
+        })
+        .collect();
+
+    // the coefficients are the secret key and the randomness hiding it
+    polynomial.coeffs.zeroize();
+    Ok((shares, verification_keys))
+}
+
+/// Dealer-side key generation: samples a fresh key pair and shares its secret key.
+pub fn keygen<C: CurveGroup, R: Rng>(
+    pp: &Parameters<C>,
+    threshold: usize,
+    parties: usize,
+    rng: &mut R,
+) -> Result<(PublicKey<C>, Shares<C>), ark_crypto_primitives::Error> {
+    let sk = SecretKey::<C>(C::ScalarField::rand(rng));
+    let pk = pp.generator.mul(sk.0).into();
+    let shares = share_secret_key(pp, &sk, threshold, parties, rng)?;
+    Ok((pk, shares))
+}
+
+impl<C: CurveGroup> SecretKeyShare<C> {
+    pub fn partial_decrypt<R: Rng>(
+        &self,
+        pp: &Parameters<C>,
+        ciphertext: &Ciphertext<C>,
+        rng: &mut R,
+    ) -> PartialDecryption<C> {
+        PartialDecryption {
+            index: self.index,
+            value: ciphertext.0.mul(self.share).into(),
+            proof: prove_dleq::<C, R>(&pp.generator, &ciphertext.0, &self.share, rng),
+        }
+    }
+}
+
+/// Lagrange coefficients at zero for the given evaluation points.
+fn lagrange_coefficients<F: PrimeField>(indices: &[u64]) -> Vec<F> {
+    indices
+        .iter()
+        .map(|&i| {
+            let x_i = F::from(i);
+            let (num, den) = indices.iter().filter(|&&j| j != i).fold(
+                (F::one(), F::one()),
+                |(num, den), &j| {
+                    let x_j = F::from(j);
+                    (num * x_j, den * (x_j - x_i))
+                },
+            );
+            num * den.inverse().unwrap()
+        })
+        .collect()
+}
+
+/// Recovers the plaintext from at least `threshold` partial decryptions of `ciphertext`.
+///
+/// Every partial decryption is checked against its verification key, including those beyond
+/// the first `threshold`: a single invalid share rejects the whole set instead of being
+/// skipped. The plaintext is then interpolated from the first `threshold` shares.
+pub fn combine<C: CurveGroup>(
+    pp: &Parameters<C>,
+    threshold: usize,
+    verification_keys: &[VerificationKey<C>],
+    partials: &[PartialDecryption<C>],
+    ciphertext: &Ciphertext<C>,
+) -> Result<Plaintext<C>, ark_crypto_primitives::Error> {
+    if threshold == 0 {
+        return Err(ThresholdError::InvalidThreshold { threshold, parties: partials.len() }.into());
+    }
+
+    let mut seen = HashSet::new();
+    for partial in partials {
+        if partial.index == 0 {
+            return Err(ThresholdError::InvalidIndex(partial.index).into());
+        }
+        if !seen.insert(partial.index) {
+            return Err(ThresholdError::DuplicateIndex(partial.index).into());
+        }
+        let vk = verification_keys
+            .iter()
+            .find(|vk| vk.index == partial.index)
+            .ok_or(ThresholdError::InvalidIndex(partial.index))?;
+        // the subgroup check keeps a small-order component from slipping past the proof
+        if !partial.value.mul_bigint(C::ScalarField::MODULUS).is_zero()
+            || !verify_dleq(&pp.generator, &vk.value, &ciphertext.0, &partial.value, &partial.proof)
+        {
+            return Err(ThresholdError::InvalidPartialDecryption(partial.index).into());
+        }
+    }
+    if partials.len() < threshold {
+        return Err(ThresholdError::NotEnoughShares {
+            threshold,
+            received: partials.len(),
+        }
+        .into());
+    }
+
+    let partials = &partials[..threshold];
+    let indices = partials.iter().map(|p| p.index).collect::<Vec<_>>();
+    let s = lagrange_coefficients::<C::ScalarField>(&indices)
+        .into_iter()
+        .zip(partials)
+        .fold(C::zero(), |acc, (lambda, partial)| acc + partial.value.mul(lambda));
+
+    Ok((ciphertext.1.into_group() - s).into_affine())
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::CurveGroup;
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{combine, keygen, share_secret_key, PartialDecryption, ThresholdError};
+    use crate::gadgets::public_encryptions::elgamal::{ElGamal, Randomness};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+
+    fn error_of(result: Result<impl std::fmt::Debug, ark_crypto_primitives::Error>) -> ThresholdError {
+        result.unwrap_err().downcast_ref::<ThresholdError>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_threshold_decryption() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, (shares, vks)) = keygen::<C, _>(&parameter, 3, 5, rng).unwrap();
+
+        let msg = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+
+        for subset in [[0usize, 1, 2], [0, 2, 4], [4, 3, 1]] {
+            let partials = subset
+                .iter()
+                .map(|&i| shares[i].partial_decrypt(&parameter, &cipher, rng))
+                .collect::<Vec<_>>();
+            assert_eq!(msg, combine(&parameter, 3, &vks, &partials, &cipher).unwrap());
+        }
+
+        // two shares are not enough
+        let partials = vec![
+            shares[0].partial_decrypt(&parameter, &cipher, rng),
+            shares[1].partial_decrypt(&parameter, &cipher, rng),
+        ];
+        assert_eq!(
+            ThresholdError::NotEnoughShares { threshold: 3, received: 2 },
+            error_of(combine(&parameter, 3, &vks, &partials, &cipher)),
+        );
+    }
+
+    #[test]
+    fn test_threshold_shares_existing_key() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = ElGamal::<C>::keygen(&parameter, rng).unwrap();
+        let (shares, vks) = share_secret_key(&parameter, &sk, 2, 3, rng).unwrap();
+
+        let msg = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+
+        let partials = vec![
+            shares[2].partial_decrypt(&parameter, &cipher, rng),
+            shares[0].partial_decrypt(&parameter, &cipher, rng),
+        ];
+        assert_eq!(msg, combine(&parameter, 2, &vks, &partials, &cipher).unwrap());
+
+        assert!(share_secret_key(&parameter, &sk, 4, 3, rng).is_err());
+        assert!(share_secret_key(&parameter, &sk, 0, 3, rng).is_err());
+    }
+
+    #[test]
+    fn test_threshold_rejects_bad_shares() {
+        let rng = &mut test_rng();
+
+        let parameter = ElGamal::<C>::setup(rng).unwrap();
+        let (pk, (shares, vks)) = keygen::<C, _>(&parameter, 2, 3, rng).unwrap();
+
+        let msg = C::rand(rng).into();
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = ElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+
+        let p0 = shares[0].partial_decrypt(&parameter, &cipher, rng);
+        let p1 = shares[1].partial_decrypt(&parameter, &cipher, rng);
+        let p2 = shares[2].partial_decrypt(&parameter, &cipher, rng);
+
+        assert_eq!(
+            ThresholdError::DuplicateIndex(p0.index),
+            error_of(combine(&parameter, 2, &vks, &[p0, p0], &cipher)),
+        );
+
+        let zero_index = PartialDecryption { index: 0, ..p1 };
+        assert_eq!(
+            ThresholdError::InvalidIndex(0),
+            error_of(combine(&parameter, 2, &vks, &[p0, zero_index], &cipher)),
+        );
+
+        // a share without a verification key
+        let unknown_index = PartialDecryption { index: 4, ..p1 };
+        assert_eq!(
+            ThresholdError::InvalidIndex(4),
+            error_of(combine(&parameter, 2, &vks, &[p0, unknown_index], &cipher)),
+        );
+
+        // a point of small order is not a valid partial decryption
+        let low_order = ark_ed_on_bn254::EdwardsAffine::new_unchecked(
+            ark_ed_on_bn254::Fq::from(0u64),
+            -ark_ed_on_bn254::Fq::from(1u64),
+        );
+        let malformed = PartialDecryption { value: low_order, ..p1 };
+        assert_eq!(
+            ThresholdError::InvalidPartialDecryption(p1.index),
+            error_of(combine(&parameter, 2, &vks, &[p0, malformed], &cipher)),
+        );
+
+        // a well-formed subgroup point that is not share * c1
+        let shifted = PartialDecryption {
+            value: (p1.value + parameter.generator).into_affine(),
+            ..p1
+        };
+        assert_eq!(
+            ThresholdError::InvalidPartialDecryption(p1.index),
+            error_of(combine(&parameter, 2, &vks, &[p0, shifted], &cipher)),
+        );
+
+        // a bad share is rejected even past the first `threshold`
+        assert_eq!(
+            ThresholdError::InvalidPartialDecryption(p1.index),
+            error_of(combine(&parameter, 2, &vks, &[p0, p2, shifted], &cipher)),
+        );
+        assert_eq!(msg, combine(&parameter, 2, &vks, &[p0, p2, p1], &cipher).unwrap());
+    }
+}
@@ -0,0 +1,382 @@
+use std::{borrow::Borrow, marker::PhantomData};
+
+use ark_crypto_primitives::sponge::constraints::CryptographicSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::PoseidonConfig;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, ToConstraintField};
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::{AllocVar, AllocationMode, Boolean, CurveVar, EqGadget, FieldVar};
+use ark_r1cs_std::{R1CSVar, ToConstraintFieldGadget};
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
+
+use crate::gadgets::curves::FixedBaseMulGadget;
+use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+use crate::gadgets::public_encryptions::elgamal::constraints::{self as elgamal_constraints, PublicKeyVar, RandomnessVar};
+
+use super::*;
+
+/// The generator, plus the Poseidon configuration, which is always baked into the circuit.
+#[derive(Clone)]
+pub struct ParametersVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+where
+    C::BaseField: PrimeField,
+{
+    pub elgamal: elgamal_constraints::ParametersVar<C, GG>,
+    pub poseidon: PoseidonConfig<C::BaseField>,
+}
+
+impl<C, GG> AllocVar<Parameters<C>, C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    fn new_variable<T: Borrow<Parameters<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        // the sponge configuration is part of the circuit shape; without a value the circuit is
+        // built for the one `setup` picks
+        let prep = f().map(|p| p.borrow().clone());
+        let poseidon = prep.as_ref().map_or_else(|_| default_poseidon_config(), |p| p.poseidon.clone());
+        let elgamal =
+            elgamal_constraints::ParametersVar::new_variable(cs, || prep.as_ref().map(|p| p.elgamal()).map_err(|e| *e), mode)?;
+        Ok(Self { elgamal, poseidon })
+    }
+}
+
+/// The message elements as native field variables.
+#[derive(Clone)]
+pub struct PlaintextVar<F: PrimeField>(pub Vec<FpVar<F>>);
+
+impl<F: PrimeField> AllocVar<Vec<F>, F> for PlaintextVar<F> {
+    fn new_variable<T: Borrow<Vec<F>>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        // the message length is part of the circuit shape, so the value is needed up front;
+        // circuits that know it use `new_variable_with_len`
+        let prep = f()?.borrow().clone();
+        Self::new_variable_with_len(cs, prep.len(), || Ok(prep), mode)
+    }
+}
+
+impl<F: PrimeField> PlaintextVar<F> {
+    /// Allocates a message of `len` elements. A missing assignment is passed on to every
+    /// element, so a setup without values still succeeds.
+    pub fn new_variable_with_len<T: Borrow<Vec<F>>>(
+        cs: impl Into<Namespace<F>>,
+        len: usize,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let prep = f().map(|m| m.borrow().clone());
+        if prep.as_ref().is_ok_and(|m| m.len() != len) {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        (0..len)
+            .map(|i| FpVar::new_variable(cs.clone(), || prep.as_ref().map(|m| m[i]).map_err(|e| *e), mode))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+#[derive(Clone)]
+pub struct OutputVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+where
+    C::BaseField: PrimeField,
+{
+    pub c1: GG,
+    pub body: Vec<FpVar<C::BaseField>>,
+    pub tag: FpVar<C::BaseField>,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> AllocVar<Ciphertext<C>, C::BaseField> for OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    fn new_variable<T: Borrow<Ciphertext<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        // the body length is part of the circuit shape, so the value is needed up front;
+        // circuits that know it use `new_variable_with_len`
+        let prep = f()?.borrow().clone();
+        Self::new_variable_with_len(cs, prep.body.len(), || Ok(prep), mode)
+    }
+}
+
+impl<C, GG> OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    /// Allocates a ciphertext of a `len` element message. A missing assignment is passed on to
+    /// every component, so a setup without values still succeeds.
+    pub fn new_variable_with_len<T: Borrow<Ciphertext<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        len: usize,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let prep = f().map(|c| c.borrow().clone());
+        if prep.as_ref().is_ok_and(|c| c.body.len() != len) {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let c1 = GG::new_variable(cs.clone(), || prep.as_ref().map(|c| c.c1).map_err(|e| *e), mode)?;
+        let body = (0..len)
+            .map(|i| FpVar::new_variable(cs.clone(), || prep.as_ref().map(|c| c.body[i]).map_err(|e| *e), mode))
+            .collect::<Result<Vec<_>, _>>()?;
+        let tag = FpVar::new_variable(cs.clone(), || prep.as_ref().map(|c| c.tag).map_err(|e| *e), mode)?;
+        Ok(Self {
+            c1,
+            body,
+            tag,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> EqGadget<C::BaseField> for OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        if self.body.len() != other.body.len() {
+            return Ok(Boolean::FALSE);
+        }
+        self.c1
+            .is_eq(&other.c1)?
+            .and(&self.body.is_eq(&other.body)?)?
+            .and(&self.tag.is_eq(&other.tag)?)
+    }
+}
+
+impl<C, GG> R1CSVar<C::BaseField> for OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    type Value = Ciphertext<C>;
+
+    fn cs(&self) -> ConstraintSystemRef<C::BaseField> {
+        self.c1.cs().or(self.body.cs()).or(self.tag.cs())
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(Ciphertext {
+            c1: self.c1.value()?.into_affine(),
+            body: self.body.value()?,
+            tag: self.tag.value()?,
+        })
+    }
+}
+
+/// Proves that an `OutputVar` encrypts a witness vector to a public key, mirroring
+/// `HybridElGamal::encrypt` step by step.
+#[derive(Clone)]
+pub struct HybridElGamalEncGadget<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    #[doc(hidden)]
+    _curve: PhantomData<*const C>,
+    _group_var: PhantomData<*const GG>,
+}
+
+impl<C, GG> AsymmetricEncryptionGadget<HybridElGamal<C>, C::BaseField> for HybridElGamalEncGadget<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C> + ToConstraintFieldGadget<C::BaseField>,
+    C::BaseField: PrimeField + Absorb,
+    C::Affine: ToConstraintField<C::BaseField>,
+{
+    type OutputVar = OutputVar<C, GG>;
+    type ParametersVar = ParametersVar<C, GG>;
+    type PlaintextVar = PlaintextVar<C::BaseField>;
+    type PublicKeyVar = PublicKeyVar<C, GG>;
+    type RandomnessVar = RandomnessVar<C::BaseField>;
+
+    fn encrypt(
+        parameters: &Self::ParametersVar,
+        message: &Self::PlaintextVar,
+        randomness: &Self::RandomnessVar,
+        public_key: &Self::PublicKeyVar,
+    ) -> Result<Self::OutputVar, SynthesisError> {
+        let randomness = &randomness.0;
+        let len = message.0.len();
+
+        // compute c1 = randomness*generator
+        let c1 = parameters.elgamal.scalar_mul_generator_le(randomness)?;
+
+        // compute the shared point randomness*pk
+        let shared = public_key.pk.clone().scalar_mul_le(randomness.iter())?;
+
+        // mask the message with the key stream, then authenticate the body
+        let cs = shared.cs().or(message.0.cs());
+        let mut sponge = PoseidonSpongeVar::new(cs, &parameters.poseidon);
+        sponge.absorb(&shared.to_constraint_field()?)?;
+        sponge.absorb(&FpVar::constant(C::BaseField::from(len as u64)))?;
+        let stream = sponge.squeeze_field_elements(len)?;
+        let body: Vec<_> = message.0.iter().zip(&stream).map(|(m, k)| m + k).collect();
+        sponge.absorb(&body)?;
+        let tag = sponge.squeeze_field_elements(1)?.remove(0);
+
+        Ok(Self::OutputVar {
+            c1,
+            body,
+            tag,
+            _curve: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ed_on_bn254::{constraints::EdwardsVar, EdwardsProjective, Fq};
+    use ark_r1cs_std::prelude::{AllocVar, AllocationMode, EqGadget};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, SynthesisError, SynthesisMode};
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::gadgets::public_encryptions::conformance::check_conformance;
+    use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+    use crate::gadgets::public_encryptions::elgamal::constraints::{PublicKeyVar, RandomnessVar};
+    use crate::gadgets::public_encryptions::hybrid::constraints::{
+        HybridElGamalEncGadget, OutputVar, ParametersVar, PlaintextVar,
+    };
+    use crate::gadgets::public_encryptions::hybrid::{Ciphertext, HybridElGamal, Parameters, PublicKey, Randomness};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type MyEnc = HybridElGamal<EdwardsProjective>;
+    type MyGadget = HybridElGamalEncGadget<EdwardsProjective, EdwardsVar>;
+
+    // proves that `ct` encrypts a witness message of `len` elements to `pk`
+    struct HybridCircuit {
+        parameters: Parameters<EdwardsProjective>,
+        len: usize,
+        pk: Option<PublicKey<EdwardsProjective>>,
+        ct: Option<Ciphertext<EdwardsProjective>>,
+        msg: Option<Vec<Fq>>,
+        r: Option<Randomness<EdwardsProjective>>,
+    }
+
+    impl ConstraintSynthesizer<Fq> for HybridCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fq>) -> Result<(), SynthesisError> {
+            let missing = SynthesisError::AssignmentMissing;
+            let parameters = ParametersVar::new_constant(cs.clone(), &self.parameters)?;
+            let pk = PublicKeyVar::new_input(cs.clone(), || self.pk.ok_or(missing))?;
+            let ct = OutputVar::new_variable_with_len(cs.clone(), self.len, || self.ct.ok_or(missing), AllocationMode::Input)?;
+            let msg = PlaintextVar::new_variable_with_len(cs.clone(), self.len, || self.msg.ok_or(missing), AllocationMode::Witness)?;
+            let r = RandomnessVar::new_witness(cs.clone(), || self.r.ok_or(missing))?;
+
+            MyGadget::encrypt(&parameters, &msg, &r, &pk)?.enforce_equal(&ct)
+        }
+    }
+
+    #[test]
+    fn test_hybrid_gadget() {
+        let rng = &mut test_rng();
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg: Vec<Fq> = (0..4).map(|_| Fq::rand(rng)).collect();
+        let randomness = Randomness::rand(rng);
+        let primitive_result = MyEnc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let parameters_var = ParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+        let pk_var = PublicKeyVar::new_input(cs.clone(), || Ok(&pk)).unwrap();
+        let expected_var = OutputVar::new_input(cs.clone(), || Ok(&primitive_result)).unwrap();
+        let msg_var = PlaintextVar::new_witness(cs.clone(), || Ok(&msg)).unwrap();
+        let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+
+        let result_var = MyGadget::encrypt(&parameters_var, &msg_var, &randomness_var, &pk_var).unwrap();
+        assert_eq!(primitive_result, result_var.value().unwrap());
+        result_var.enforce_equal(&expected_var).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // a different witness message does not match the public ciphertext
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let parameters_var = ParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+        let pk_var = PublicKeyVar::new_input(cs.clone(), || Ok(&pk)).unwrap();
+        let expected_var = OutputVar::new_input(cs.clone(), || Ok(&primitive_result)).unwrap();
+        let mut other = msg.clone();
+        other[2] += Fq::from(1u64);
+        let msg_var = PlaintextVar::new_witness(cs.clone(), || Ok(&other)).unwrap();
+        let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+
+        let result_var = MyGadget::encrypt(&parameters_var, &msg_var, &randomness_var, &pk_var).unwrap();
+        result_var.enforce_equal(&expected_var).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_hybrid_conformance() {
+        let rng = &mut test_rng();
+        let report = check_conformance::<MyEnc, MyGadget, Fq, _>(rng, |rng| (0..3).map(|_| Fq::rand(rng)).collect(), 2);
+        // regression guard on ed_on_bn254: update deliberately when the gadget changes
+        assert_eq!(
+            (6214, 9, 6071),
+            (report.num_constraints, report.num_instance_variables, report.num_witness_variables)
+        );
+    }
+
+    #[test]
+    fn test_hybrid_circuit_setup_mode() {
+        let rng = &mut test_rng();
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg: Vec<Fq> = (0..3).map(|_| Fq::rand(rng)).collect();
+        let randomness = Randomness::rand(rng);
+        let ct = MyEnc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let circuit = HybridCircuit {
+            parameters: parameters.clone(),
+            len: msg.len(),
+            pk: Some(pk),
+            ct: Some(ct.clone()),
+            msg: Some(msg.clone()),
+            r: Some(randomness),
+        };
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // the setup builds the same circuit without any values
+        let setup_cs = ConstraintSystem::<Fq>::new_ref();
+        setup_cs.set_mode(SynthesisMode::Setup);
+        let circuit = HybridCircuit {
+            parameters: parameters.clone(),
+            len: msg.len(),
+            pk: None,
+            ct: None,
+            msg: None,
+            r: None,
+        };
+        circuit.generate_constraints(setup_cs.clone()).unwrap();
+        assert_eq!(cs.num_constraints(), setup_cs.num_constraints());
+        assert_eq!(cs.num_instance_variables(), setup_cs.num_instance_variables());
+        assert_eq!(cs.num_witness_variables(), setup_cs.num_witness_variables());
+
+        // a value that disagrees with the declared length is rejected
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        assert!(PlaintextVar::new_variable_with_len(cs.clone(), 2, || Ok(&msg), AllocationMode::Witness).is_err());
+        assert!(OutputVar::<EdwardsProjective, EdwardsVar>::new_variable_with_len(cs, 2, || Ok(&ct), AllocationMode::Input).is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use std::marker::PhantomData;
+use std::ops::Mul;
+
+use ark_crypto_primitives::sponge::poseidon::{PoseidonConfig, PoseidonSponge};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ec::CurveGroup;
+use ark_ff::{PrimeField, ToConstraintField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use rand::Rng;
+
+use crate::gadgets::hashes::poseidon;
+
+use super::AsymmetricEncryptionScheme;
+use super::elgamal;
+
+pub mod constraints;
+
+pub use super::elgamal::{PublicKey, Randomness, SecretKey};
+
+/// ElGamal key encapsulation with a Poseidon data encapsulation, encrypting a vector of
+/// base field elements.
+///
+/// The KEM sends `c1 = r*G` and both sides derive the shared point `r*pk = sk*c1`. A Poseidon
+/// duplex sponge absorbs the shared point and the message length, squeezes one key stream
+/// element per message element, then absorbs the masked body and squeezes an authentication tag.
+pub struct HybridElGamal<C: CurveGroup> {
+    _group: PhantomData<C>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Parameters<C: CurveGroup>
+where
+    C::BaseField: PrimeField,
+{
+    pub generator: C::Affine,
+    pub poseidon: PoseidonConfig<C::BaseField>,
+}
+
+impl<C: CurveGroup> Parameters<C>
+where
+    C::BaseField: PrimeField,
+{
+    /// The plain ElGamal parameters sharing the same generator.
+    pub fn elgamal(&self) -> elgamal::Parameters<C> {
+        elgamal::Parameters {
+            generator: self.generator,
+        }
+    }
+}
+
+/// The two-input instance of `hashes::poseidon`, i.e. circomlib's width-3 Poseidon when the
+/// base field is BN254's scalar field.
+pub fn default_poseidon_config<F: PrimeField>() -> PoseidonConfig<F> {
+    poseidon::grain_config(2)
+}
+
+pub type Plaintext<C> = Vec<<C as CurveGroup>::BaseField>;
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Ciphertext<C: CurveGroup> {
+    pub c1: C::Affine,
+    pub body: Vec<C::BaseField>,
+    pub tag: C::BaseField,
+}
+
+// the DEM sponge keyed by the shared point and the message length, ready to squeeze the key stream
+fn dem_sponge<C>(
+    pp: &Parameters<C>,
+    shared: &C::Affine,
+    len: usize,
+) -> Result<PoseidonSponge<C::BaseField>, ark_crypto_primitives::Error>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField + Absorb,
+    C::Affine: ToConstraintField<C::BaseField>,
+{
+    let shared = shared
+        .to_field_elements()
+        .ok_or("shared point has no field element encoding")?;
+
+    let mut sponge = PoseidonSponge::new(&pp.poseidon);
+    sponge.absorb(&shared);
+    sponge.absorb(&C::BaseField::from(len as u64));
+    Ok(sponge)
+}
+
+impl<C: CurveGroup> AsymmetricEncryptionScheme for HybridElGamal<C>
+where
+    C::ScalarField: PrimeField,
+    C::BaseField: PrimeField + Absorb,
+    C::Affine: ToConstraintField<C::BaseField>,
+{
+    type Parameters = Parameters<C>;
+    type PublicKey = PublicKey<C>;
+    type SecretKey = SecretKey<C>;
+    type Randomness = Randomness<C>;
+    type Plaintext = Plaintext<C>;
+    type Ciphertext = Ciphertext<C>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, ark_crypto_primitives::Error> {
+        let generator = C::rand(rng).into();
+        Ok(Parameters {
+            generator,
+            poseidon: default_poseidon_config(),
+        })
+    }
+
+    fn keygen<R: Rng>(
+        pp: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), ark_crypto_primitives::Error> {
+        let secret_key: C::ScalarField = C::ScalarField::rand(rng);
+        let public_key = pp.generator.mul(secret_key).into();
+        Ok((public_key, SecretKey(secret_key)))
+    }
+
+    fn encrypt(
+        pp: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &Self::Plaintext,
+        r: &Self::Randomness,
+    ) -> Result<Self::Ciphertext, ark_crypto_primitives::Error> {
+        let c1 = pp.generator.mul(r.0).into();
+        let shared = pk.mul(r.0).into();
+
+        let mut sponge = dem_sponge(pp, &shared, message.len())?;
+        let stream: Vec<C::BaseField> = sponge.squeeze_field_elements(message.len());
+        let body: Vec<_> = message.iter().zip(&stream).map(|(m, k)| *m + k).collect();
+        sponge.absorb(&body);
+        let tag = sponge.squeeze_field_elements(1)[0];
+
+        Ok(Ciphertext { c1, body, tag })
+    }
+
+    fn decrypt(
+        pp: &Self::Parameters,
+        sk: &Self::SecretKey,
+        ciphertext: &Self::Ciphertext,
+    ) -> Result<Self::Plaintext, ark_crypto_primitives::Error> {
+        let shared = ciphertext.c1.mul(sk.0).into();
+
+        let mut sponge = dem_sponge(pp, &shared, ciphertext.body.len())?;
+        let stream: Vec<C::BaseField> = sponge.squeeze_field_elements(ciphertext.body.len());
+        sponge.absorb(&ciphertext.body);
+        if sponge.squeeze_field_elements::<C::BaseField>(1)[0] != ciphertext.tag {
+            return Err("ciphertext authentication failed".into());
+        }
+
+        Ok(ciphertext.body.iter().zip(&stream).map(|(c, k)| *c - k).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{HybridElGamal, Randomness};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+    type F = ark_ed_on_bn254::Fq;
+
+    #[test]
+    fn test_hybrid_encryption() {
+        let rng = &mut test_rng();
+
+        let parameters = HybridElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = HybridElGamal::<C>::keygen(&parameters, rng).unwrap();
+
+        for len in [0, 1, 2, 5] {
+            let msg: Vec<F> = (0..len).map(|_| F::rand(rng)).collect();
+            let r: Randomness<C> = Randomness::rand(rng);
+
+            let cipher = HybridElGamal::<C>::encrypt(&parameters, &pk, &msg, &r).unwrap();
+            assert_eq!(len, cipher.body.len());
+            assert_eq!(msg, HybridElGamal::<C>::decrypt(&parameters, &sk, &cipher).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_hybrid_rejects_tampering() {
+        let rng = &mut test_rng();
+
+        let parameters = HybridElGamal::<C>::setup(rng).unwrap();
+        let (pk, sk) = HybridElGamal::<C>::keygen(&parameters, rng).unwrap();
+        let (_, other_sk) = HybridElGamal::<C>::keygen(&parameters, rng).unwrap();
+        let msg: Vec<F> = (0..3).map(|_| F::rand(rng)).collect();
+        let cipher = HybridElGamal::<C>::encrypt(&parameters, &pk, &msg, &Randomness::rand(rng)).unwrap();
+
+        let mut body = cipher.clone();
+        body.body[1] += F::from(1u64);
+        assert!(HybridElGamal::<C>::decrypt(&parameters, &sk, &body).is_err());
+
+        let mut truncated = cipher.clone();
+        truncated.body.pop();
+        assert!(HybridElGamal::<C>::decrypt(&parameters, &sk, &truncated).is_err());
+
+        let mut tag = cipher.clone();
+        tag.tag += F::from(1u64);
+        assert!(HybridElGamal::<C>::decrypt(&parameters, &sk, &tag).is_err());
+
+        assert!(HybridElGamal::<C>::decrypt(&parameters, &other_sk, &cipher).is_err());
+    }
+}
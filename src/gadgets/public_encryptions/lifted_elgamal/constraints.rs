@@ -0,0 +1,228 @@
+use std::{borrow::Borrow, marker::PhantomData};
+
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::{AllocVar, AllocationMode, Boolean, CurveVar};
+use ark_relations::r1cs::{Namespace, SynthesisError};
+
+use crate::gadgets::curves::FixedBaseMulGadget;
+use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+use crate::gadgets::public_encryptions::elgamal::constraints::{self as elgamal_constraints, OutputVar, PublicKeyVar, RandomnessVar};
+
+use super::*;
+
+/// A plaintext `v` with `0 <= v < 2^k`, where `k = bits.len()`.
+///
+/// `value` is the same integer packed into the constraint field, so other constraints can use
+/// it directly. A plaintext outside the range is truncated to its low `k` bits, which leaves any
+/// circuit comparing the resulting ciphertext against the real one unsatisfied.
+#[derive(Clone)]
+pub struct PlaintextVar<F: PrimeField> {
+    pub bits: Vec<Boolean<F>>,
+    pub value: FpVar<F>,
+}
+
+impl<F: PrimeField> PlaintextVar<F> {
+    /// Allocates a plaintext in `[0, 2^range_bits)`. The range only has to fit below both field
+    /// moduli, so the packed `value` and the scalar never wrap; it can exceed `MAX_RANGE_BITS`,
+    /// which limits native decryption, not the circuit. Other ranges are `Unsatisfiable`.
+    pub fn new_bounded<S: PrimeField>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<S, SynthesisError>,
+        range_bits: u32,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        if range_bits == 0 || range_bits >= F::MODULUS_BIT_SIZE.min(S::MODULUS_BIT_SIZE) {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let ns = cs.into();
+        let cs = ns.cs();
+        let value = f().map(|v| v.into_bigint().to_bits_le());
+        let bits = (0..range_bits as usize)
+            .map(|i| Boolean::new_variable(cs.clone(), || value.as_ref().map(|b| b[i]).map_err(|e| *e), mode))
+            .collect::<Result<Vec<_>, _>>()?;
+        let value = Boolean::le_bits_to_fp_var(&bits)?;
+
+        Ok(Self { bits, value })
+    }
+}
+
+impl<S, F> AllocVar<S, F> for PlaintextVar<F>
+where
+    S: PrimeField,
+    F: PrimeField,
+{
+    /// Allocates a plaintext in `[0, 2^DEFAULT_RANGE_BITS)`.
+    fn new_variable<T: Borrow<S>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        Self::new_bounded(cs, || f().map(|v| *v.borrow()), DEFAULT_RANGE_BITS, mode)
+    }
+}
+
+/// The generator of the plain ElGamal parameters; the decryption table stays outside the circuit.
+#[derive(Clone)]
+pub struct ParametersVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>(pub elgamal_constraints::ParametersVar<C, GG>);
+
+impl<C, GG> AllocVar<Parameters<C>, C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn new_variable<T: Borrow<Parameters<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let value = f().map(|p| p.borrow().elgamal());
+        elgamal_constraints::ParametersVar::new_variable(cs, || value, mode).map(Self)
+    }
+}
+
+#[derive(Clone)]
+pub struct LiftedElGamalEncGadget<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    #[doc(hidden)]
+    _curve: PhantomData<*const C>,
+    _group_var: PhantomData<*const GG>,
+}
+
+impl<C, GG> AsymmetricEncryptionGadget<LiftedElGamal<C>, C::BaseField> for LiftedElGamalEncGadget<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+    C::BaseField: PrimeField,
+{
+    type OutputVar = OutputVar<C, GG>;
+    type ParametersVar = ParametersVar<C, GG>;
+    type PlaintextVar = PlaintextVar<C::BaseField>;
+    type PublicKeyVar = PublicKeyVar<C, GG>;
+    type RandomnessVar = RandomnessVar<C::BaseField>;
+
+    fn encrypt(
+        parameters: &Self::ParametersVar,
+        message: &Self::PlaintextVar,
+        randomness: &Self::RandomnessVar,
+        public_key: &Self::PublicKeyVar,
+    ) -> Result<Self::OutputVar, SynthesisError> {
+        let randomness = &randomness.0;
+
+        // compute s = randomness*pk
+        let s = public_key.pk.clone().scalar_mul_le(randomness.iter())?;
+
+        // compute c1 = randomness*generator
+        let c1 = parameters.0.scalar_mul_generator_le(randomness)?;
+
+        // compute c2 = v*generator + s
+        let c2 = parameters.0.scalar_mul_generator_le(&message.bits)? + s;
+
+        Ok(Self::OutputVar {
+            c1,
+            c2,
+            _curve: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::CurveGroup;
+    use ark_ff::PrimeField;
+    use ark_std::{test_rng, UniformRand};
+
+    use ark_ed_on_bn254::{constraints::EdwardsVar, EdwardsProjective, Fq, Fr};
+    use ark_r1cs_std::fields::fp::FpVar;
+    use ark_r1cs_std::prelude::{AllocVar, AllocationMode, EqGadget};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+    use crate::gadgets::public_encryptions::elgamal::constraints::{OutputVar, PublicKeyVar, RandomnessVar};
+    use crate::gadgets::public_encryptions::lifted_elgamal::{
+        constraints::{LiftedElGamalEncGadget, ParametersVar, PlaintextVar},
+        LiftedElGamal, Parameters, Randomness,
+    };
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type MyEnc = LiftedElGamal<EdwardsProjective>;
+    type MyGadget = LiftedElGamalEncGadget<EdwardsProjective, EdwardsVar>;
+
+    // encrypts `value` natively and checks the gadget reproduces it from a `range_bits` plaintext
+    fn check(value: u64, range_bits: u32) -> bool {
+        let rng = &mut test_rng();
+
+        let parameters = Parameters::<EdwardsProjective>::new(EdwardsProjective::rand(rng).into_affine(), 8).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let randomness = Randomness::rand(rng);
+        let primitive_result = MyEnc::encrypt(&parameters, &pk, &Fr::from(value), &randomness).unwrap();
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let parameters_var = ParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+        let pk_var = PublicKeyVar::new_input(cs.clone(), || Ok(&pk)).unwrap();
+        let randomness_var = RandomnessVar::new_witness(cs.clone(), || Ok(&randomness)).unwrap();
+        let msg_var =
+            PlaintextVar::new_bounded(cs.clone(), || Ok(Fr::from(value)), range_bits, AllocationMode::Witness).unwrap();
+
+        let result_var = MyGadget::encrypt(&parameters_var, &msg_var, &randomness_var, &pk_var).unwrap();
+        let expected_var = OutputVar::new_input(cs.clone(), || Ok(&primitive_result)).unwrap();
+        expected_var.enforce_equal(&result_var).unwrap();
+
+        // the plaintext is usable as a field element by the rest of the circuit; it holds the low
+        // `range_bits` bits, so only the ciphertext comparison can notice a value out of range
+        let truncated = value as u128 % (1u128 << range_bits);
+        let claimed = FpVar::new_input(cs.clone(), || Ok(Fq::from(truncated))).unwrap();
+        msg_var.value.enforce_equal(&claimed).unwrap();
+
+        if truncated == value as u128 {
+            assert_eq!(primitive_result.1, result_var.c2.value().unwrap());
+            assert_eq!(Fq::from(value), msg_var.value.value().unwrap());
+        }
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn test_lifted_elgamal_gadget() {
+        assert!(check(0, 8));
+        assert!(check(200, 8));
+        assert!(check(255, 8));
+        assert!(check(40_000, 16));
+    }
+
+    #[test]
+    fn test_lifted_elgamal_gadget_range() {
+        assert!(!check(256, 8));
+        assert!(!check(1 << 16, 16));
+        // the same values fit one bit wider
+        assert!(check(256, 9));
+        assert!(check(1 << 16, 17));
+
+        // the circuit is not limited by the decryption table
+        assert!(check(u64::MAX, 64));
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        for range_bits in [0, Fr::MODULUS_BIT_SIZE] {
+            assert!(PlaintextVar::new_bounded(cs.clone(), || Ok(Fr::from(1u64)), range_bits, AllocationMode::Witness).is_err());
+        }
+        assert!(PlaintextVar::new_bounded(cs, || Ok(Fr::from(1u64)), Fr::MODULUS_BIT_SIZE - 1, AllocationMode::Witness).is_ok());
+    }
+
+    #[test]
+    fn test_lifted_elgamal_default_range() {
+        let rng = &mut test_rng();
+
+        let parameters = MyEnc::setup(rng).unwrap();
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let msg_var = <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::PlaintextVar::new_witness(
+            cs.clone(),
+            || Ok(Fr::from(u32::MAX)),
+        )
+        .unwrap();
+        assert_eq!(parameters.table.range_bits() as usize, msg_var.bits.len());
+        assert_eq!(Fq::from(u32::MAX), msg_var.value.value().unwrap());
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::ops::Mul;
+use std::sync::Arc;
+
+use ark_crypto_primitives::Error;
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ff::PrimeField;
+use ark_std::UniformRand;
+use rand::Rng;
+
+use super::AsymmetricEncryptionScheme;
+use super::elgamal;
+
+pub mod constraints;
+
+pub use super::elgamal::{Ciphertext, PublicKey, Randomness, SecretKey};
+
+/// Range used by `setup`: plaintexts in `[0, 2^32)` can be decrypted.
+pub const DEFAULT_RANGE_BITS: u32 = 32;
+
+/// Largest supported range. The table then holds `2^20` points, on the order of a hundred
+/// megabytes, and a decryption takes up to `2^20` giant steps.
+pub const MAX_RANGE_BITS: u32 = 40;
+
+/// ElGamal "in the exponent": a scalar `m` is encrypted as `(r*G, m*G + r*pk)`.
+/// Decryption recovers `m*G` and solves the discrete log with baby-step giant-step,
+/// so only plaintexts inside the range of the parameters' table can be recovered.
+pub struct LiftedElGamal<C: CurveGroup> {
+    _group: PhantomData<C>,
+}
+
+/// Precomputed baby steps `{j*G : 0 <= j < 2^ceil(bits/2)}` for plaintexts in `[0, 2^bits)`.
+pub struct DiscreteLogTable<C: CurveGroup> {
+    range_bits: u32,
+    baby_steps: HashMap<C::Affine, u64>,
+    giant_step: C,
+}
+
+impl<C: CurveGroup> DiscreteLogTable<C> {
+    /// Fails unless `0 < range_bits <= MAX_RANGE_BITS`.
+    pub fn new(generator: &C::Affine, range_bits: u32) -> Result<Self, Error> {
+        if range_bits == 0 || range_bits > MAX_RANGE_BITS {
+            return Err(format!("unsupported decryption range of {} bits", range_bits).into());
+        }
+
+        let baby_count = 1u64 << range_bits.div_ceil(2);
+
+        let mut points = Vec::with_capacity(baby_count as usize);
+        let mut acc = C::zero();
+        for _ in 0..baby_count {
+            points.push(acc);
+            acc += generator;
+        }
+
+        let baby_steps = C::normalize_batch(&points)
+            .into_iter()
+            .enumerate()
+            .map(|(j, p)| (p, j as u64))
+            .collect();
+
+        Ok(Self {
+            range_bits,
+            baby_steps,
+            giant_step: acc,
+        })
+    }
+
+    pub fn range_bits(&self) -> u32 {
+        self.range_bits
+    }
+
+    /// Finds `m` in `[0, 2^range_bits)` with `point = m*G`.
+    pub fn discrete_log(&self, point: C) -> Option<u64> {
+        let bound = 1u64 << self.range_bits;
+        let baby_count = self.baby_steps.len() as u64;
+        let giant_count = bound.div_ceil(baby_count);
+
+        let mut current = point;
+        for i in 0..giant_count {
+            if let Some(j) = self.baby_steps.get(&current.into_affine()) {
+                let m = i * baby_count + j;
+                return (m < bound).then_some(m);
+            }
+            current -= self.giant_step;
+        }
+        None
+    }
+}
+
+#[derive(Clone)]
+pub struct Parameters<C: CurveGroup> {
+    pub generator: C::Affine,
+    pub table: Arc<DiscreteLogTable<C>>,
+}
+
+impl<C: CurveGroup> Parameters<C> {
+    /// Builds parameters whose decryption table covers plaintexts in `[0, 2^range_bits)`.
+    pub fn new(generator: C::Affine, range_bits: u32) -> Result<Self, Error> {
+        let table = Arc::new(DiscreteLogTable::new(&generator, range_bits)?);
+        Ok(Self { generator, table })
+    }
+
+    /// The plain ElGamal parameters sharing the same generator.
+    pub fn elgamal(&self) -> elgamal::Parameters<C> {
+        elgamal::Parameters {
+            generator: self.generator,
+        }
+    }
+}
+
+pub type Plaintext<C> = <C as Group>::ScalarField;
+
+impl<C: CurveGroup> AsymmetricEncryptionScheme for LiftedElGamal<C>
+where
+    C::ScalarField: PrimeField {
+        type Parameters = Parameters<C>;
+        type PublicKey = PublicKey<C>;
+        type SecretKey = SecretKey<C>;
+        type Randomness = Randomness<C>;
+        type Plaintext = Plaintext<C>;
+        type Ciphertext = Ciphertext<C>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, ark_crypto_primitives::Error> {
+        let generator = C::rand(rng).into();
+        Parameters::new(generator, DEFAULT_RANGE_BITS)
+    }
+
+    fn keygen<R: Rng>(
+        pp: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), ark_crypto_primitives::Error> {
+        let secret_key: C::ScalarField = C::ScalarField::rand(rng);
+        let public_key = pp.generator.mul(secret_key).into();
+        Ok((public_key, SecretKey(secret_key)))
+    }
+
+    fn encrypt(
+        pp: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &Self::Plaintext,
+        r: &Self::Randomness,
+    ) -> Result<Self::Ciphertext, ark_crypto_primitives::Error> {
+        let s = pk.mul(r.0);
+        let c1 = pp.generator.mul(r.0).into();
+        let c2 = (pp.generator.mul(*message) + s).into();
+
+        Ok(Ciphertext(c1, c2))
+    }
+
+    fn decrypt(
+        pp: &Self::Parameters,
+        sk: &Self::SecretKey,
+        ciphertext: &Self::Ciphertext,
+    ) -> Result<Self::Plaintext, ark_crypto_primitives::Error> {
+        let c1: <C as CurveGroup>::Affine = ciphertext.0;
+        let c2: <C as CurveGroup>::Affine = ciphertext.1;
+
+        let m = c2.into_group() - c1.mul(sk.0);
+
+        pp.table
+            .discrete_log(m)
+            .map(C::ScalarField::from)
+            .ok_or_else(|| "plaintext is outside the range of the discrete log table".into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::CurveGroup;
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{LiftedElGamal, Parameters, Randomness, MAX_RANGE_BITS};
+
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+    type F = <C as ark_ec::Group>::ScalarField;
+
+    #[test]
+    fn test_lifted_elgamal_encryption() {
+        let rng = &mut test_rng();
+
+        let parameter = Parameters::<C>::new(C::rand(rng).into_affine(), 16).unwrap();
+        let (pk, sk) = LiftedElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        for value in [0u64, 1, 255, 256, 12345, (1 << 16) - 1] {
+            let msg = F::from(value);
+            let r: Randomness<C> = Randomness::rand(rng);
+
+            let cipher = LiftedElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+            let check_msg = LiftedElGamal::<C>::decrypt(&parameter, &sk, &cipher).unwrap();
+
+            assert_eq!(msg, check_msg);
+        }
+    }
+
+    #[test]
+    fn test_lifted_elgamal_tally() {
+        let rng = &mut test_rng();
+
+        let parameter = Parameters::<C>::new(C::rand(rng).into_affine(), 16).unwrap();
+        let (pk, sk) = LiftedElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let votes = [1u64, 0, 1, 1, 0, 1];
+        let tally = votes
+            .iter()
+            .map(|v| {
+                let r: Randomness<C> = Randomness::rand(rng);
+                LiftedElGamal::<C>::encrypt(&parameter, &pk, &F::from(*v), &r).unwrap()
+            })
+            .reduce(|acc, ct| acc + ct)
+            .unwrap();
+        assert_eq!(F::from(4u64), LiftedElGamal::<C>::decrypt(&parameter, &sk, &tally).unwrap());
+
+        let balance = LiftedElGamal::<C>::encrypt(&parameter, &pk, &F::from(100u64), &Randomness::rand(rng)).unwrap();
+        let spend = LiftedElGamal::<C>::encrypt(&parameter, &pk, &F::from(30u64), &Randomness::rand(rng)).unwrap();
+        let remaining = (balance - spend) * F::from(3u64);
+        assert_eq!(F::from(210u64), LiftedElGamal::<C>::decrypt(&parameter, &sk, &remaining).unwrap());
+    }
+
+    #[test]
+    fn test_lifted_elgamal_odd_range() {
+        let rng = &mut test_rng();
+
+        let parameter = Parameters::<C>::new(C::rand(rng).into_affine(), 9).unwrap();
+        let (pk, sk) = LiftedElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = LiftedElGamal::<C>::encrypt(&parameter, &pk, &F::from(511u64), &r).unwrap();
+        assert_eq!(F::from(511u64), LiftedElGamal::<C>::decrypt(&parameter, &sk, &cipher).unwrap());
+
+        let cipher = LiftedElGamal::<C>::encrypt(&parameter, &pk, &F::from(512u64), &r).unwrap();
+        assert!(LiftedElGamal::<C>::decrypt(&parameter, &sk, &cipher).is_err());
+    }
+
+    #[test]
+    fn test_lifted_elgamal_out_of_range() {
+        let rng = &mut test_rng();
+
+        let parameter = Parameters::<C>::new(C::rand(rng).into_affine(), 8).unwrap();
+        let (pk, sk) = LiftedElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = LiftedElGamal::<C>::encrypt(&parameter, &pk, &-F::from(1u64), &r).unwrap();
+
+        assert!(LiftedElGamal::<C>::decrypt(&parameter, &sk, &cipher).is_err());
+    }
+
+    #[test]
+    fn test_lifted_elgamal_unsupported_range() {
+        let rng = &mut test_rng();
+
+        let generator = C::rand(rng).into_affine();
+        assert!(Parameters::<C>::new(generator, 0).is_err());
+        assert!(Parameters::<C>::new(generator, MAX_RANGE_BITS + 1).is_err());
+    }
+}
@@ -1,8 +1,13 @@
 
 use ark_crypto_primitives::Error;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod conformance;
 pub mod constraints;
 pub mod elgamal;
+pub mod hybrid;
+pub mod lifted_elgamal;
+pub mod twisted_elgamal;
 
 use ark_std::rand::Rng;
 
@@ -0,0 +1,285 @@
+use std::{borrow::Borrow, marker::PhantomData};
+
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_r1cs_std::prelude::{AllocVar, AllocationMode, Boolean, CurveVar, EqGadget};
+use ark_relations::r1cs::{Namespace, SynthesisError};
+
+use crate::gadgets::curves::{fixed_base_scalar_mul_le, window_tables, FixedBaseMulGadget, FixedBaseTable};
+use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+use crate::gadgets::public_encryptions::elgamal::constraints::{alloc_scalar_bits_le, PublicKeyVar, RandomnessVar};
+
+use super::*;
+
+/// Little-endian bits of the scalar plaintext.
+#[derive(Clone)]
+pub struct PlaintextVar<F: PrimeField>(pub Vec<Boolean<F>>);
+
+impl<S, F> AllocVar<S, F> for PlaintextVar<F>
+where
+    S: PrimeField,
+    F: PrimeField,
+{
+    fn new_variable<T: Borrow<S>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        alloc_scalar_bits_le(cs, f().map(|m| *m.borrow()), mode).map(Self)
+    }
+}
+
+#[derive(Clone)]
+pub struct ParametersVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    pub g: GG,
+    pub h: GG,
+    /// Window tables of `G` and `H`, precomputed when the parameters are allocated as a constant.
+    pub g_table: Option<FixedBaseTable<C>>,
+    pub h_table: Option<FixedBaseTable<C>>,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> AllocVar<Parameters<C>, C::BaseField> for ParametersVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn new_variable<T: Borrow<Parameters<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let prep = f().map(|p| (p.borrow().g, p.borrow().h));
+        let g = GG::new_variable(cs.clone(), || prep.map(|p| p.0), mode)?;
+        let h = GG::new_variable(cs.clone(), || prep.map(|p| p.1), mode)?;
+
+        // constant generators let scalar multiplications use precomputed lookup tables
+        let (g_table, h_table) = match (mode, prep) {
+            (AllocationMode::Constant, Ok((g, h))) => (Some(window_tables::<C>(&g)), Some(window_tables::<C>(&h))),
+            _ => (None, None),
+        };
+
+        Ok(Self {
+            g,
+            h,
+            g_table,
+            h_table,
+            _curve: PhantomData,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct OutputVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    pub handle: GG,
+    pub commitment: GG,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> AllocVar<Ciphertext<C>, C::BaseField> for OutputVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn new_variable<T: Borrow<Ciphertext<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let prep = f().map(|g| *g.borrow());
+        let handle = GG::new_variable(cs.clone(), || prep.map(|g| g.handle), mode)?;
+        let commitment = GG::new_variable(cs.clone(), || prep.map(|g| g.commitment), mode)?;
+        Ok(Self {
+            handle,
+            commitment,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GC> EqGadget<C::BaseField> for OutputVar<C, GC>
+where
+    C: CurveGroup,
+    GC: CurveVar<C, C::BaseField>,
+{
+    #[inline]
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        self.handle.is_eq(&other.handle)?.and(&self.commitment.is_eq(&other.commitment)?)
+    }
+}
+
+#[derive(Clone)]
+pub struct TwistedElGamalEncGadget<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    #[doc(hidden)]
+    _curve: PhantomData<*const C>,
+    _group_var: PhantomData<*const GG>,
+}
+
+impl<C, GG> AsymmetricEncryptionGadget<TwistedElGamal<C>, C::BaseField> for TwistedElGamalEncGadget<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C>,
+    C::BaseField: PrimeField,
+{
+    type OutputVar = OutputVar<C, GG>;
+    type ParametersVar = ParametersVar<C, GG>;
+    type PlaintextVar = PlaintextVar<C::BaseField>;
+    type PublicKeyVar = PublicKeyVar<C, GG>;
+    type RandomnessVar = RandomnessVar<C::BaseField>;
+
+    fn encrypt(
+        parameters: &Self::ParametersVar,
+        message: &Self::PlaintextVar,
+        randomness: &Self::RandomnessVar,
+        public_key: &Self::PublicKeyVar,
+    ) -> Result<Self::OutputVar, SynthesisError> {
+        let randomness = &randomness.0;
+
+        // compute handle = randomness*pk
+        let handle = public_key.pk.clone().scalar_mul_le(randomness.iter())?;
+
+        // compute commitment = m*G + randomness*H
+        let m_g = fixed_base_scalar_mul_le(&parameters.g, parameters.g_table.as_ref(), &message.0)?;
+        let r_h = fixed_base_scalar_mul_le(&parameters.h, parameters.h_table.as_ref(), randomness)?;
+        let commitment = m_g + r_h;
+
+        Ok(Self::OutputVar {
+            handle,
+            commitment,
+            _curve: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::{test_rng, UniformRand};
+
+    use ark_ed_on_bn254::{constraints::EdwardsVar, EdwardsProjective, Fq, Fr};
+
+    use crate::gadgets::public_encryptions::constraints::AsymmetricEncryptionGadget;
+    use crate::gadgets::public_encryptions::twisted_elgamal::{constraints::TwistedElGamalEncGadget, Parameters, Randomness, TwistedElGamal};
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_r1cs_std::R1CSVar;
+    use ark_r1cs_std::alloc::AllocVar;
+    use ark_r1cs_std::eq::EqGadget;
+
+    type MyEnc = TwistedElGamal<EdwardsProjective>;
+    type MyGadget = TwistedElGamalEncGadget<EdwardsProjective, EdwardsVar>;
+
+    #[test]
+    fn test_twisted_elgamal_gadget() {
+        let rng = &mut test_rng();
+
+        // compute primitive result
+        let parameters = Parameters::<EdwardsProjective>::derive(b"test", 8).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let msg = Fr::from(173u64);
+        let randomness = Randomness::rand(rng);
+        let primitive_result = MyEnc::encrypt(&parameters, &pk, &msg, &randomness).unwrap();
+
+        // construct constraint system
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let randomness_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::RandomnessVar::new_witness(
+                ark_relations::ns!(cs, "gadget_randomness"),
+                || Ok(&randomness),
+            )
+            .unwrap();
+        let parameters_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::ParametersVar::new_constant(
+                ark_relations::ns!(cs, "gadget_parameters"),
+                &parameters,
+            )
+            .unwrap();
+        let msg_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::PlaintextVar::new_witness(
+                ark_relations::ns!(cs, "gadget_message"),
+                || Ok(msg),
+            )
+            .unwrap();
+        let pk_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::PublicKeyVar::new_input(
+                ark_relations::ns!(cs, "gadget_public_key"),
+                || Ok(&pk),
+            )
+            .unwrap();
+
+        // use gadget
+        let result_var =
+            MyGadget::encrypt(&parameters_var, &msg_var, &randomness_var, &pk_var).unwrap();
+
+        // check that result equals expected ciphertext in the constraint system
+        let expected_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::OutputVar::new_input(
+                ark_relations::ns!(cs, "gadget_expected"),
+                || Ok(&primitive_result),
+            )
+            .unwrap();
+        assert_eq!(primitive_result.handle, result_var.handle.value().unwrap());
+        assert_eq!(primitive_result.commitment, result_var.commitment.value().unwrap());
+        expected_var.enforce_equal(&result_var).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+
+        // witness parameters have no lookup tables and take the generic multiplication
+        let witness_cs = ConstraintSystem::<Fq>::new_ref();
+        let parameters_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::ParametersVar::new_witness(witness_cs.clone(), || Ok(&parameters))
+                .unwrap();
+        let msg_var = <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::PlaintextVar::new_witness(witness_cs.clone(), || Ok(msg))
+            .unwrap();
+        let randomness_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::RandomnessVar::new_witness(witness_cs.clone(), || Ok(&randomness))
+                .unwrap();
+        let pk_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::PublicKeyVar::new_input(witness_cs.clone(), || Ok(&pk)).unwrap();
+        let result_var = MyGadget::encrypt(&parameters_var, &msg_var, &randomness_var, &pk_var).unwrap();
+        assert_eq!(primitive_result.commitment, result_var.commitment.value().unwrap());
+        assert!(witness_cs.is_satisfied().unwrap());
+        assert!(cs.num_constraints() < witness_cs.num_constraints());
+    }
+
+    #[test]
+    fn test_twisted_elgamal_gadget_wrong_plaintext() {
+        let rng = &mut test_rng();
+
+        let parameters = Parameters::<EdwardsProjective>::derive(b"test", 8).unwrap();
+        let (pk, _) = MyEnc::keygen(&parameters, rng).unwrap();
+        let randomness = Randomness::rand(rng);
+        let primitive_result = MyEnc::encrypt(&parameters, &pk, &Fr::from(173u64), &randomness).unwrap();
+
+        // a prover claiming a different plaintext under the same randomness cannot match the ciphertext
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let randomness_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::RandomnessVar::new_witness(cs.clone(), || Ok(&randomness))
+                .unwrap();
+        let parameters_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::ParametersVar::new_constant(cs.clone(), &parameters)
+                .unwrap();
+        let msg_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::PlaintextVar::new_witness(cs.clone(), || Ok(Fr::from(174u64)))
+                .unwrap();
+        let pk_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::PublicKeyVar::new_input(cs.clone(), || Ok(&pk)).unwrap();
+
+        let result_var =
+            MyGadget::encrypt(&parameters_var, &msg_var, &randomness_var, &pk_var).unwrap();
+        let expected_var =
+            <MyGadget as AsymmetricEncryptionGadget<MyEnc, Fq>>::OutputVar::new_input(cs.clone(), || Ok(&primitive_result))
+                .unwrap();
+        assert_eq!(primitive_result.handle, result_var.handle.value().unwrap());
+        assert_ne!(primitive_result.commitment, result_var.commitment.value().unwrap());
+        expected_var.enforce_equal(&result_var).unwrap();
+
+        assert!(!cs.is_satisfied().unwrap());
+    }
+}
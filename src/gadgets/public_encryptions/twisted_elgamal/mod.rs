@@ -0,0 +1,212 @@
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::Arc;
+
+use ark_crypto_primitives::Error;
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ff::{Field, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use rand::Rng;
+
+use super::elgamal::hash_to_curve;
+use super::lifted_elgamal::{DiscreteLogTable, DEFAULT_RANGE_BITS};
+use super::AsymmetricEncryptionScheme;
+
+pub use super::elgamal::{Randomness, SecretKey};
+
+pub mod constraints;
+
+/// Domain separation string for the default generators `G` and `H`.
+pub const DEFAULT_DOMAIN: &[u8] = b"arkworks-study/twisted-elgamal/generators/v1";
+
+/// Twisted ElGamal: a scalar `m` is encrypted as `(r*pk, m*G + r*H)` with `pk = sk*H`.
+/// The second component is a Pedersen commitment to `m`, the first a decryption handle.
+pub struct TwistedElGamal<C: CurveGroup> {
+    _group: PhantomData<C>,
+}
+
+#[derive(Clone)]
+pub struct Parameters<C: CurveGroup> {
+    pub g: C::Affine,
+    pub h: C::Affine,
+    pub table: Arc<DiscreteLogTable<C>>,
+}
+
+impl<C: CurveGroup> Parameters<C> {
+    /// Derives `G` and `H` by hashing to the curve, so nobody knows `log_G(H)` and the
+    /// commitment stays binding. `range_bits` bounds the plaintexts `decrypt` can recover and
+    /// must lie in `(0, lifted_elgamal::MAX_RANGE_BITS]`.
+    pub fn derive(domain: &[u8], range_bits: u32) -> Result<Self, Error> {
+        let g = hash_to_curve::<C>(domain, b"G");
+        let h = hash_to_curve::<C>(domain, b"H");
+        let table = Arc::new(DiscreteLogTable::new(&g, range_bits)?);
+        Ok(Self { g, h, table })
+    }
+}
+
+pub type PublicKey<C> = <C as CurveGroup>::Affine;
+
+pub type Plaintext<C> = <C as Group>::ScalarField;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Ciphertext<C: CurveGroup> {
+    /// `r*pk`
+    pub handle: C::Affine,
+    /// `m*G + r*H`
+    pub commitment: C::Affine,
+}
+
+impl<C: CurveGroup> Add for Ciphertext<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Ciphertext {
+            handle: (self.handle + other.handle).into(),
+            commitment: (self.commitment + other.commitment).into(),
+        }
+    }
+}
+
+impl<C: CurveGroup> Sub for Ciphertext<C> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<C: CurveGroup> Neg for Ciphertext<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Ciphertext {
+            handle: (-self.handle.into_group()).into(),
+            commitment: (-self.commitment.into_group()).into(),
+        }
+    }
+}
+
+impl<C: CurveGroup> Mul<C::ScalarField> for Ciphertext<C> {
+    type Output = Self;
+
+    fn mul(self, scalar: C::ScalarField) -> Self {
+        Ciphertext {
+            handle: self.handle.mul(scalar).into(),
+            commitment: self.commitment.mul(scalar).into(),
+        }
+    }
+}
+
+impl<C: CurveGroup> AsymmetricEncryptionScheme for TwistedElGamal<C>
+where
+    C::ScalarField: PrimeField {
+        type Parameters = Parameters<C>;
+        type PublicKey = PublicKey<C>;
+        type SecretKey = SecretKey<C>;
+        type Randomness = Randomness<C>;
+        type Plaintext = Plaintext<C>;
+        type Ciphertext = Ciphertext<C>;
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, ark_crypto_primitives::Error> {
+        Parameters::derive(DEFAULT_DOMAIN, DEFAULT_RANGE_BITS)
+    }
+
+    fn keygen<R: Rng>(
+        pp: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), ark_crypto_primitives::Error> {
+        let secret_key: C::ScalarField = C::ScalarField::rand(rng);
+        let public_key = pp.h.mul(secret_key).into();
+        Ok((public_key, SecretKey(secret_key)))
+    }
+
+    fn encrypt(
+        pp: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &Self::Plaintext,
+        r: &Self::Randomness,
+    ) -> Result<Self::Ciphertext, ark_crypto_primitives::Error> {
+        let handle = pk.mul(r.0).into();
+        let commitment = (pp.g.mul(*message) + pp.h.mul(r.0)).into();
+
+        Ok(Ciphertext { handle, commitment })
+    }
+
+    fn decrypt(
+        pp: &Self::Parameters,
+        sk: &Self::SecretKey,
+        ciphertext: &Self::Ciphertext,
+    ) -> Result<Self::Plaintext, ark_crypto_primitives::Error> {
+        let sk_inv = sk.0.inverse().ok_or("secret key is zero")?;
+
+        // r*H = sk^{-1} * (r*pk)
+        let blinding = ciphertext.handle.mul(sk_inv);
+        let m = ciphertext.commitment.into_group() - blinding;
+
+        pp.table
+            .discrete_log(m)
+            .map(C::ScalarField::from)
+            .ok_or_else(|| "plaintext is outside the range of the discrete log table".into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::Mul;
+
+    use ark_ec::CurveGroup;
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{Parameters, Randomness, TwistedElGamal};
+    use crate::gadgets::public_encryptions::lifted_elgamal::MAX_RANGE_BITS;
+    use crate::gadgets::public_encryptions::AsymmetricEncryptionScheme;
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+    type F = <C as ark_ec::Group>::ScalarField;
+
+    #[test]
+    fn test_twisted_elgamal_encryption() {
+        let rng = &mut test_rng();
+
+        let parameter = Parameters::<C>::derive(b"test", 16).unwrap();
+        let (pk, sk) = TwistedElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let msg = F::from(4242u64);
+        let r: Randomness<C> = Randomness::rand(rng);
+        let cipher = TwistedElGamal::<C>::encrypt(&parameter, &pk, &msg, &r).unwrap();
+
+        assert_eq!(msg, TwistedElGamal::<C>::decrypt(&parameter, &sk, &cipher).unwrap());
+
+        // the second component is a Pedersen commitment to the plaintext
+        assert_eq!(cipher.commitment, (parameter.g.mul(msg) + parameter.h.mul(r.0)).into_affine());
+    }
+
+    #[test]
+    fn test_twisted_elgamal_homomorphism() {
+        let rng = &mut test_rng();
+
+        let parameter = Parameters::<C>::derive(b"test", 16).unwrap();
+        let (pk, sk) = TwistedElGamal::<C>::keygen(&parameter, rng).unwrap();
+
+        let r1: Randomness<C> = Randomness::rand(rng);
+        let r2: Randomness<C> = Randomness::rand(rng);
+        let ct1 = TwistedElGamal::<C>::encrypt(&parameter, &pk, &F::from(500u64), &r1).unwrap();
+        let ct2 = TwistedElGamal::<C>::encrypt(&parameter, &pk, &F::from(120u64), &r2).unwrap();
+
+        let sum = ct1 + ct2;
+        assert_eq!(F::from(620u64), TwistedElGamal::<C>::decrypt(&parameter, &sk, &sum).unwrap());
+        // the combined ciphertext is the encryption under the combined randomness
+        let r_sum = Randomness(r1.0 + r2.0);
+        assert_eq!(sum, TwistedElGamal::<C>::encrypt(&parameter, &pk, &F::from(620u64), &r_sum).unwrap());
+
+        let difference = (ct1 - ct2) * F::from(2u64);
+        assert_eq!(F::from(760u64), TwistedElGamal::<C>::decrypt(&parameter, &sk, &difference).unwrap());
+    }
+
+    #[test]
+    fn test_twisted_elgamal_unsupported_range() {
+        assert!(Parameters::<C>::derive(b"test", 0).is_err());
+        assert!(Parameters::<C>::derive(b"test", MAX_RANGE_BITS + 1).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! BLAKE-512, the SHA-3 finalist (not BLAKE2b), which circomlib uses to expand EdDSA keys.
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+const U: [u64; 16] = [
+    0x243f6a8885a308d3, 0x13198a2e03707344, 0xa4093822299f31d0, 0x082efa98ec4e6c89,
+    0x452821e638d01377, 0xbe5466cf34e90c6c, 0xc0ac29b7c97c50dd, 0x3f84d5b5b5470917,
+    0x9216d5d98979fb1b, 0xd1310ba698dfb5ac, 0x2ffd72dbd01adfb7, 0xb8e1afed6a267e96,
+    0xba7c9045f12c7f99, 0x24a19947b3916cf7, 0x0801f2e2858efc16, 0x636920d871574e69,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const ROUNDS: usize = 16;
+const BLOCK_LEN: usize = 128;
+
+// the column and diagonal steps of a round, as (a, b, c, d) indices into the state
+const STEPS: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+// `counter` is the number of message bits up to the end of this block, 0 for a padding-only block
+fn compress(h: &mut [u64; 8], block: &[u8; BLOCK_LEN], counter: u128) {
+    let m: [u64; 16] = std::array::from_fn(|i| u64::from_be_bytes(block[8 * i..8 * i + 8].try_into().unwrap()));
+    let (t0, t1) = (counter as u64, (counter >> 64) as u64);
+
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&U[..8]);
+    v[12] ^= t0;
+    v[13] ^= t0;
+    v[14] ^= t1;
+    v[15] ^= t1;
+
+    for r in 0..ROUNDS {
+        let s = &SIGMA[r % 10];
+        for (i, &[a, b, c, d]) in STEPS.iter().enumerate() {
+            let (x, y) = (s[2 * i], s[2 * i + 1]);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[x] ^ U[y]);
+            v[d] = (v[d] ^ v[a]).rotate_right(32);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(25);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[y] ^ U[x]);
+            v[d] = (v[d] ^ v[a]).rotate_right(16);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(11);
+        }
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+pub(crate) fn blake512(data: &[u8]) -> [u8; 64] {
+    let mut h = IV;
+    let bits = data.len() as u128 * 8;
+
+    // every full block that is followed by more data or by the padding
+    let mut chunks = data.chunks_exact(BLOCK_LEN);
+    let mut processed = 0u128;
+    for chunk in &mut chunks {
+        processed += BLOCK_LEN as u128 * 8;
+        compress(&mut h, chunk.try_into().unwrap(), processed);
+    }
+
+    // message || 1 || 0* || 1 || 128-bit big-endian length
+    let rest = chunks.remainder();
+    let mut block = [0u8; BLOCK_LEN];
+    block[..rest.len()].copy_from_slice(rest);
+    block[rest.len()] = 0x80;
+    let counter = if rest.is_empty() { 0 } else { bits };
+    if rest.len() > BLOCK_LEN - 17 {
+        // no room for the length; it goes into an extra block without message bits
+        compress(&mut h, &block, counter);
+        block = [0u8; BLOCK_LEN];
+        block[BLOCK_LEN - 17] = 0x01;
+        block[BLOCK_LEN - 16..].copy_from_slice(&bits.to_be_bytes());
+        compress(&mut h, &block, 0);
+    } else {
+        block[BLOCK_LEN - 17] |= 0x01;
+        block[BLOCK_LEN - 16..].copy_from_slice(&bits.to_be_bytes());
+        compress(&mut h, &block, counter);
+    }
+
+    let mut out = [0u8; 64];
+    for (chunk, word) in out.chunks_exact_mut(8).zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::blake512;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn test_blake512_vectors() {
+        // from the BLAKE submission: the empty message, one zero byte and 144 zero bytes
+        assert_eq!(
+            hex(&blake512(b"")),
+            "a8cfbbd73726062df0c6864dda65defe58ef0cc52a5625090fa17601e1eecd1b628e94f396ae402a00acc9eab77b4d4c2e852aaaa25a636d80af3fc7913ef5b8"
+        );
+        assert_eq!(
+            hex(&blake512(&[0u8])),
+            "97961587f6d970faba6d2478045de6d1fabd09b61ae50932054d52bc29d31be4ff9102b9f69e2bbdb83be13d4b9c06091e5fa0b48bd081b634058be0ec49beb3"
+        );
+        assert_eq!(
+            hex(&blake512(&[0u8; 144])),
+            "313717d608e9cf758dcb1eb0f0c3cf9fc150b2d500fb33f51c52afc99d358a2f1374b8a38bba7974e7f6ef79cab16f22ce1e649d6e01ad9589c213045d545dde"
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::borrow::Borrow;
+use std::sync::OnceLock;
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ed_on_bn254::constraints::EdwardsVar;
+use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fq};
+use ark_ff::Field;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::{AllocVar, AllocationMode, Boolean, CurveVar, EqGadget, FieldVar, ToBitsGadget};
+use ark_r1cs_std::R1CSVar;
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
+
+use crate::gadgets::curves::{fixed_base_scalar_mul_le, window_tables, FixedBaseTable};
+use crate::gadgets::hashes::poseidon;
+use crate::gadgets::public_encryptions::elgamal::constraints::{alloc_scalar_bits_le, scalar_from_bits_le};
+
+use super::*;
+
+pub type PublicKeyVar = EdwardsVar;
+pub type MessageVar = FpVar<Fq>;
+
+// the window tables of Base8, built once for every verification
+fn base8_table() -> &'static FixedBaseTable<EdwardsProjective> {
+    static TABLE: OnceLock<FixedBaseTable<EdwardsProjective>> = OnceLock::new();
+    TABLE.get_or_init(|| window_tables::<EdwardsProjective>(&base8()))
+}
+
+/// Allocates a point of a key or signature checking only that it is on the curve, as circomlib
+/// does. `EdwardsVar::new_witness` would also enforce subgroup membership, which rejects keys
+/// with a small-order component that `verify` accepts once it clears the cofactor.
+pub fn alloc_point(
+    cs: impl Into<Namespace<Fq>>,
+    value: Result<EdwardsAffine, SynthesisError>,
+    mode: AllocationMode,
+) -> Result<EdwardsVar, SynthesisError> {
+    EdwardsVar::new_variable_omit_prime_order_check(cs, || value.map(|p| p.into_group()), mode)
+}
+
+#[derive(Clone)]
+pub struct SignatureVar {
+    pub r8: EdwardsVar,
+    /// Little-endian bits of `S`; allocating them enforces `S < l` as circomlib does.
+    pub s: Vec<Boolean<Fq>>,
+}
+
+impl AllocVar<Signature, Fq> for SignatureVar {
+    fn new_variable<T: Borrow<Signature>>(
+        cs: impl Into<Namespace<Fq>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let signature = f().map(|s| s.borrow().clone());
+        let r8 = alloc_point(cs.clone(), signature.clone().map(|s| s.r8), mode)?;
+        let s = alloc_scalar_bits_le(cs, signature.map(|s| s.s), mode)?;
+        Ok(Self { r8, s })
+    }
+}
+
+impl R1CSVar<Fq> for SignatureVar {
+    type Value = Signature;
+
+    fn cs(&self) -> ConstraintSystemRef<Fq> {
+        self.r8.cs().or(self.s.cs())
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(Signature {
+            r8: self.r8.value()?.into_affine(),
+            s: scalar_from_bits_le(&self.s.value()?)?,
+        })
+    }
+}
+
+impl EqGadget<Fq> for SignatureVar {
+    fn is_eq(&self, other: &Self) -> Result<Boolean<Fq>, SynthesisError> {
+        self.r8.is_eq(&other.r8)?.and(&self.s.is_eq(&other.s)?)
+    }
+}
+
+/// The in-circuit counterpart of `EdDSAPoseidon::verify`, following circomlib's
+/// `EdDSAPoseidonVerifier`.
+pub struct EdDSAPoseidonVerifyGadget;
+
+impl EdDSAPoseidonVerifyGadget {
+    // circomlib's coordinates of a point; the x scaling is a constant, so this is free
+    fn circom_coordinates(point: &EdwardsVar) -> [FpVar<Fq>; 2] {
+        let inverse_scale = x_scale().inverse().expect("the scale is non-zero");
+        [&point.x * inverse_scale, point.y.clone()]
+    }
+
+    /// In-circuit `challenge`.
+    pub fn challenge(r8: &EdwardsVar, pk: &PublicKeyVar, message: &MessageVar) -> Result<FpVar<Fq>, SynthesisError> {
+        let mut inputs = Self::circom_coordinates(r8).to_vec();
+        inputs.extend(Self::circom_coordinates(pk));
+        inputs.push(message.clone());
+        poseidon::constraints::hash(&inputs)
+    }
+
+    /// Whether `S*Base8 = R8 + (8*e)*A` for `e = Poseidon(R8, A, m)` and `A.x != 0`. The result
+    /// is left to the caller to enforce, like circomlib's `enabled` input.
+    ///
+    /// `pk` should come from `alloc_point`, so that a small-order component of `A` is cleared
+    /// here rather than rejected at allocation.
+    pub fn verify(pk: &PublicKeyVar, message: &MessageVar, signature: &SignatureVar) -> Result<Boolean<Fq>, SynthesisError> {
+        let e = Self::challenge(&signature.r8, pk, message)?.to_bits_le()?;
+
+        // S*Base8, through the window tables of Base8
+        let base8_var = EdwardsVar::constant(base8().into_group());
+        let lhs = fixed_base_scalar_mul_le(&base8_var, Some(base8_table()), &signature.s)?;
+
+        let pk8 = pk.double()?.double()?.double()?;
+        let rhs = signature.r8.clone() + pk8.scalar_mul_le(e.iter())?;
+
+        lhs.is_eq(&rhs)?.and(&pk.x.is_zero()?.not())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::CurveGroup;
+    use ark_ed_on_bn254::{EdwardsAffine, Fq, Fr};
+    use ark_r1cs_std::fields::fp::FpVar;
+    use ark_r1cs_std::prelude::{AllocVar, AllocationMode, Boolean, EqGadget};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::gadgets::signatures::eddsa::constraints::{alloc_point, EdDSAPoseidonVerifyGadget, SignatureVar};
+    use crate::gadgets::signatures::eddsa::test::circomlib_vector;
+    use crate::gadgets::signatures::eddsa::{base8, challenge, from_circom, to_scalar, EdDSAPoseidon, Signature};
+
+    #[test]
+    fn test_eddsa_poseidon_gadget() {
+        let rng = &mut test_rng();
+
+        // the circomlib test vector, imported through its coordinates
+        let (_, vector_message, vector_pk, r8, s) = circomlib_vector();
+        let vector_pk = from_circom(vector_pk.0, vector_pk.1).unwrap();
+        let vector_signature = Signature {
+            r8: from_circom(r8.0, r8.1).unwrap(),
+            s,
+        };
+
+        let (pk, sk) = EdDSAPoseidon::keygen(rng);
+        let message = Fq::rand(rng);
+        let signature = EdDSAPoseidon::sign(&sk, &message);
+        let forged = Signature {
+            s: signature.s + Fr::from(1u64),
+            ..signature.clone()
+        };
+
+        let cases = [
+            (vector_pk, vector_message, vector_signature.clone(), true),
+            (vector_pk, vector_message + Fq::from(1u64), vector_signature, false),
+            (pk, message, signature.clone(), true),
+            (vector_pk, message, signature, false),
+            (pk, message, forged, false),
+        ];
+        for (pk, message, signature, valid) in cases {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let pk_var = alloc_point(cs.clone(), Ok(pk), AllocationMode::Input).unwrap();
+            let message_var = FpVar::new_input(cs.clone(), || Ok(message)).unwrap();
+            let signature_var = SignatureVar::new_witness(cs.clone(), || Ok(&signature)).unwrap();
+            assert_eq!(signature, signature_var.value().unwrap());
+
+            let e = EdDSAPoseidonVerifyGadget::challenge(&signature_var.r8, &pk_var, &message_var).unwrap();
+            assert_eq!(challenge(&signature.r8, &pk, &message), e.value().unwrap());
+
+            let result = EdDSAPoseidonVerifyGadget::verify(&pk_var, &message_var, &signature_var).unwrap();
+            assert_eq!(valid, result.value().unwrap());
+            assert_eq!(valid, EdDSAPoseidon::verify(&pk, &message, &signature));
+            result.enforce_equal(&Boolean::TRUE).unwrap();
+            assert_eq!(valid, cs.is_satisfied().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_eddsa_poseidon_gadget_torsioned_key() {
+        let rng = &mut test_rng();
+
+        // `A = x*Base8 + T` for the point `T = (0, -1)` of order two, which `8*A` clears
+        let x = Fr::rand(rng);
+        let torsion = EdwardsAffine::new_unchecked(Fq::from(0u64), -Fq::from(1u64));
+        let pk = (base8() * x + torsion).into_affine();
+        assert!(pk.is_on_curve() && !pk.is_in_correct_subgroup_assuming_on_curve());
+
+        let message = Fq::rand(rng);
+        let r = Fr::rand(rng);
+        let r8 = (base8() * r).into_affine();
+        let e = to_scalar(&challenge(&r8, &pk, &message));
+        let signature = Signature {
+            r8,
+            s: r + e * Fr::from(8u64) * x,
+        };
+        assert!(EdDSAPoseidon::verify(&pk, &message, &signature));
+
+        for mode in [AllocationMode::Input, AllocationMode::Witness] {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let pk_var = alloc_point(cs.clone(), Ok(pk), mode).unwrap();
+            // `EdwardsVar::value` asserts subgroup membership, so compare the coordinates
+            assert_eq!((pk.x, pk.y), (pk_var.x.value().unwrap(), pk_var.y.value().unwrap()));
+            let message_var = FpVar::new_input(cs.clone(), || Ok(message)).unwrap();
+            let signature_var = SignatureVar::new_witness(cs.clone(), || Ok(&signature)).unwrap();
+
+            let result = EdDSAPoseidonVerifyGadget::verify(&pk_var, &message_var, &signature_var).unwrap();
+            result.enforce_equal(&Boolean::TRUE).unwrap();
+            assert!(cs.is_satisfied().unwrap());
+        }
+
+        // points off the curve are still rejected
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let off_curve = EdwardsAffine::new_unchecked(Fq::from(1u64), Fq::from(1u64));
+        let _ = alloc_point(cs.clone(), Ok(off_curve), AllocationMode::Witness).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+use std::ops::Mul;
+use std::sync::OnceLock;
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ed_on_bn254::{EdwardsAffine, Fq, Fr};
+use ark_ff::{BigInt, BigInteger, Field, MontFp, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use rand::Rng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::gadgets::hashes::poseidon;
+
+use self::blake512::blake512;
+
+mod blake512;
+pub mod constraints;
+
+/// circomlib's `Base8`, in its own coordinates: the generator of the prime order subgroup.
+pub const CIRCOM_BASE8: (Fq, Fq) = (
+    MontFp!("5299619240641551281634865583518297030282874472190772894086521144482721001553"),
+    MontFp!("16950150798460657717958625567821834550301663161624707787222815936182638968203"),
+);
+
+/// EdDSA over BabyJubJub with a Poseidon challenge, bit-compatible with circomlib's
+/// `signPoseidon`/`verifyPoseidon` and the `EdDSAPoseidonVerifier` template.
+///
+/// circomlib writes the curve as `168700*x^2 + y^2 = 1 + 168696*x^2*y^2`, while `ed_on_bn254`
+/// uses the isomorphic `a = 1` form, so points cross over with `from_circom`/`to_circom`. Keys
+/// and signatures are held in ark coordinates; hashing uses circomlib's.
+pub struct EdDSAPoseidon;
+
+// `sqrt(168700)`: a circomlib point `(x, y)` is `(sqrt(168700)*x, y)` on `ed_on_bn254`
+fn x_scale() -> Fq {
+    static SCALE: OnceLock<Fq> = OnceLock::new();
+    *SCALE.get_or_init(|| Fq::from(168700u64).sqrt().expect("168700 is a square"))
+}
+
+/// Maps a point in circomlib's coordinates onto `ed_on_bn254`, or `None` if it is not on the curve.
+pub fn from_circom(x: Fq, y: Fq) -> Option<EdwardsAffine> {
+    let point = EdwardsAffine::new_unchecked(x * x_scale(), y);
+    point.is_on_curve().then_some(point)
+}
+
+/// The inverse of `from_circom`.
+pub fn to_circom(point: &EdwardsAffine) -> (Fq, Fq) {
+    (point.x / x_scale(), point.y)
+}
+
+pub fn base8() -> EdwardsAffine {
+    from_circom(CIRCOM_BASE8.0, CIRCOM_BASE8.1).expect("Base8 is on the curve")
+}
+
+/// The 32 byte private key circomlib expands with BLAKE-512.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey(pub [u8; 32]);
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(<redacted>)")
+    }
+}
+
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretKey {}
+
+impl UniformRand for SecretKey {
+    fn rand<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        SecretKey(rng.gen())
+    }
+}
+
+pub type PublicKey = EdwardsAffine;
+
+/// A message is one field element, as circomlib's `signPoseidon` takes it.
+pub type Message = Fq;
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Signature {
+    pub r8: EdwardsAffine,
+    /// `S`, which circomlib requires to be below the subgroup order.
+    pub s: Fr,
+}
+
+// circomlib's `pruneBuffer` on the first half of `blake512(sk)`, read as a little-endian integer
+fn expand(sk: &SecretKey) -> ([u8; 32], [u8; 64]) {
+    let h = blake512(&sk.0);
+    let mut s = [0u8; 32];
+    s.copy_from_slice(&h[..32]);
+    s[0] &= 0xf8;
+    s[31] &= 0x7f;
+    s[31] |= 0x40;
+    (s, h)
+}
+
+/// `Poseidon(R8, A, m)`, with both points in circomlib's coordinates.
+pub fn challenge(r8: &EdwardsAffine, pk: &PublicKey, message: &Message) -> Fq {
+    let (rx, ry) = to_circom(r8);
+    let (ax, ay) = to_circom(pk);
+    // five inputs always have an instance
+    poseidon::hash(&[rx, ry, ax, ay, *message]).expect("Poseidon(5) is supported")
+}
+
+fn to_scalar(e: &Fq) -> Fr {
+    Fr::from_le_bytes_mod_order(&e.into_bigint().to_bytes_le())
+}
+
+impl EdDSAPoseidon {
+    pub fn keygen<R: Rng>(rng: &mut R) -> (PublicKey, SecretKey) {
+        let sk = SecretKey::rand(rng);
+        (Self::public_key(&sk), sk)
+    }
+
+    /// `A = (s >> 3) * Base8`, circomlib's `prv2pub`.
+    pub fn public_key(sk: &SecretKey) -> PublicKey {
+        let (s, _) = expand(sk);
+        let mut shifted = BigInt::<4>::new(std::array::from_fn(|i| u64::from_le_bytes(s[8 * i..8 * i + 8].try_into().unwrap())));
+        shifted.divn(3);
+        base8().mul_bigint(shifted).into_affine()
+    }
+
+    /// Deterministic signing as circomlib's `signPoseidon`: `r = blake512(h[32..] || m)`,
+    /// `R8 = r*Base8` and `S = r + Poseidon(R8, A, m)*s mod l`.
+    pub fn sign(sk: &SecretKey, message: &Message) -> Signature {
+        let (s, h) = expand(sk);
+        let pk = Self::public_key(sk);
+
+        let mut nonce_input = h[32..].to_vec();
+        nonce_input.extend(message.into_bigint().to_bytes_le());
+        let r = Fr::from_le_bytes_mod_order(&blake512(&nonce_input));
+        let r8 = base8().mul(r).into_affine();
+
+        let e = to_scalar(&challenge(&r8, &pk, message));
+        Signature {
+            r8,
+            s: r + e * Fr::from_le_bytes_mod_order(&s),
+        }
+    }
+
+    /// circomlib's `verifyPoseidon`: `S*Base8 = R8 + (8*Poseidon(R8, A, m))*A`. Like the circuit,
+    /// this rejects public keys with `x = 0`, and accepts keys outside the prime order subgroup,
+    /// whose small-order component the factor 8 clears.
+    pub fn verify(pk: &PublicKey, message: &Message, signature: &Signature) -> bool {
+        if !pk.is_on_curve() || !signature.r8.is_on_curve() || pk.x.is_zero() {
+            return false;
+        }
+        let e = to_scalar(&challenge(&signature.r8, pk, message));
+        base8().mul(signature.s) == signature.r8.into_group() + pk.mul_by_cofactor_to_group().mul(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use ark_ed_on_bn254::{Fq, Fr};
+    use ark_ff::PrimeField;
+    use ark_std::{test_rng, UniformRand};
+
+    use super::{base8, from_circom, to_circom, EdDSAPoseidon, SecretKey, Signature};
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    // circomlibjs `test/eddsa.js`, "Sign (using Poseidon) a single number"
+    pub(crate) fn circomlib_vector() -> (SecretKey, Fq, (Fq, Fq), (Fq, Fq), Fr) {
+        let sk = SecretKey(from_hex("0001020304050607080900010203040506070809000102030405060708090001").try_into().unwrap());
+        let message = Fq::from_le_bytes_mod_order(&from_hex("000102030405060708090000"));
+        let pk = (
+            Fq::from_str("13277427435165878497778222415993513565335242147425444199013288855685581939618").unwrap(),
+            Fq::from_str("13622229784656158136036771217484571176836296686641868549125388198837476602820").unwrap(),
+        );
+        let r8 = (
+            Fq::from_str("11384336176656855268977457483345535180380036354188103142384839473266348197733").unwrap(),
+            Fq::from_str("15383486972088797283337779941324724402501462225528836549661220478783371668959").unwrap(),
+        );
+        let s = Fr::from_str("1672775540645840396591609181675628451599263765380031905495115170613215233181").unwrap();
+        (sk, message, pk, r8, s)
+    }
+
+    #[test]
+    fn test_circomlib_vector() {
+        let (sk, message, pk, r8, s) = circomlib_vector();
+
+        let public_key = EdDSAPoseidon::public_key(&sk);
+        assert_eq!(pk, to_circom(&public_key));
+        assert_eq!(Some(public_key), from_circom(pk.0, pk.1));
+
+        let signature = EdDSAPoseidon::sign(&sk, &message);
+        assert_eq!(r8, to_circom(&signature.r8));
+        assert_eq!(s, signature.s);
+
+        let imported = Signature {
+            r8: from_circom(r8.0, r8.1).unwrap(),
+            s,
+        };
+        assert!(EdDSAPoseidon::verify(&public_key, &message, &imported));
+    }
+
+    #[test]
+    fn test_eddsa_poseidon() {
+        let rng = &mut test_rng();
+
+        assert!(base8().is_in_correct_subgroup_assuming_on_curve());
+        assert_eq!(None, from_circom(Fq::from(1u64), Fq::from(1u64)));
+
+        let (pk, sk) = EdDSAPoseidon::keygen(rng);
+        let (other_pk, _) = EdDSAPoseidon::keygen(rng);
+        let message = Fq::rand(rng);
+        let signature = EdDSAPoseidon::sign(&sk, &message);
+        assert!(EdDSAPoseidon::verify(&pk, &message, &signature));
+
+        // signing is deterministic
+        assert_eq!(signature, EdDSAPoseidon::sign(&sk, &message));
+
+        assert!(!EdDSAPoseidon::verify(&pk, &(message + Fq::from(1u64)), &signature));
+        assert!(!EdDSAPoseidon::verify(&other_pk, &message, &signature));
+        let forged = Signature {
+            s: signature.s + Fr::from(1u64),
+            ..signature.clone()
+        };
+        assert!(!EdDSAPoseidon::verify(&pk, &message, &forged));
+    }
+}
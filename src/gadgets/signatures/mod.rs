@@ -0,0 +1,2 @@
+pub mod eddsa;
+pub mod schnorr;
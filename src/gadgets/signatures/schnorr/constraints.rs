@@ -0,0 +1,181 @@
+use std::{borrow::Borrow, marker::PhantomData};
+
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::{AllocVar, AllocationMode, Boolean, CurveVar, EqGadget, ToBitsGadget};
+use ark_r1cs_std::{R1CSVar, ToConstraintFieldGadget};
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
+
+use crate::gadgets::curves::FixedBaseMulGadget;
+use crate::gadgets::hashes::poseidon;
+use crate::gadgets::public_encryptions::elgamal::constraints::{alloc_scalar_bits_le, scalar_from_bits_le};
+
+pub use crate::gadgets::public_encryptions::elgamal::constraints::{ParametersVar, PublicKeyVar};
+
+use super::*;
+
+pub type MessageVar<F> = FpVar<F>;
+
+#[derive(Clone)]
+pub struct SignatureVar<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    pub r: GG,
+    /// Little-endian bits of `s`.
+    pub s: Vec<Boolean<C::BaseField>>,
+    pub _curve: PhantomData<C>,
+}
+
+impl<C, GG> AllocVar<Signature<C>, C::BaseField> for SignatureVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+    C::BaseField: PrimeField,
+{
+    fn new_variable<T: Borrow<Signature<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let signature = f().map(|s| s.borrow().clone());
+        let r = GG::new_variable(cs.clone(), || signature.clone().map(|s| s.r), mode)?;
+        let s = alloc_scalar_bits_le(cs, signature.map(|s| s.s), mode)?;
+        Ok(Self {
+            r,
+            s,
+            _curve: PhantomData,
+        })
+    }
+}
+
+impl<C, GG> R1CSVar<C::BaseField> for SignatureVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    type Value = Signature<C>;
+
+    fn cs(&self) -> ConstraintSystemRef<C::BaseField> {
+        self.r.cs().or(self.s.cs())
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(Signature {
+            r: self.r.value()?.into_affine(),
+            s: scalar_from_bits_le(&self.s.value()?)?,
+        })
+    }
+}
+
+impl<C, GG> EqGadget<C::BaseField> for SignatureVar<C, GG>
+where
+    C: CurveGroup,
+    GG: CurveVar<C, C::BaseField>,
+{
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        self.r.is_eq(&other.r)?.and(&self.s.is_eq(&other.s)?)
+    }
+}
+
+#[derive(Clone)]
+pub struct SchnorrVerifyGadget<C: CurveGroup, GG: CurveVar<C, C::BaseField>>
+{
+    #[doc(hidden)]
+    _curve: PhantomData<*const C>,
+    _group_var: PhantomData<*const GG>,
+}
+
+impl<C, GG> SchnorrVerifyGadget<C, GG>
+where
+    C: CurveGroup,
+    GG: FixedBaseMulGadget<C> + ToConstraintFieldGadget<C::BaseField>,
+    C::BaseField: PoseidonField,
+{
+    /// In-circuit `challenge`.
+    pub fn challenge(
+        r: &GG,
+        pk: &PublicKeyVar<C, GG>,
+        message: &MessageVar<C::BaseField>,
+    ) -> Result<FpVar<C::BaseField>, SynthesisError> {
+        let mut inputs = r.to_constraint_field()?;
+        inputs.extend(pk.pk.to_constraint_field()?);
+        inputs.push(message.clone());
+        poseidon::constraints::hash(&inputs)
+    }
+
+    /// Whether `s*G = R + e*pk`, as `Schnorr::verify` checks natively. The result is left to
+    /// the caller to enforce, so a circuit can also branch on it.
+    pub fn verify(
+        parameters: &ParametersVar<C, GG>,
+        pk: &PublicKeyVar<C, GG>,
+        message: &MessageVar<C::BaseField>,
+        signature: &SignatureVar<C, GG>,
+    ) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        // the bits of the challenge are its canonical integer value, which the scalar
+        // multiplication reduces modulo the group order just like `challenge_scalar`
+        let e = Self::challenge(&signature.r, pk, message)?.to_bits_le()?;
+
+        let lhs = parameters.scalar_mul_generator_le(&signature.s)?;
+        let rhs = signature.r.clone() + pk.pk.clone().scalar_mul_le(e.iter())?;
+        lhs.is_eq(&rhs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ed_on_bn254::{constraints::EdwardsVar, EdwardsProjective, Fq, Fr};
+    use ark_r1cs_std::fields::fp::FpVar;
+    use ark_r1cs_std::prelude::{AllocVar, Boolean, EqGadget};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::{test_rng, UniformRand};
+    use rand::{RngCore, SeedableRng};
+
+    use crate::gadgets::signatures::schnorr::constraints::{
+        ParametersVar, PublicKeyVar, SchnorrVerifyGadget, SignatureVar,
+    };
+    use crate::gadgets::signatures::schnorr::{challenge, Schnorr, Signature, DEFAULT_DOMAIN};
+
+    type MySignature = Schnorr<EdwardsProjective>;
+    type MyGadget = SchnorrVerifyGadget<EdwardsProjective, EdwardsVar>;
+
+    #[test]
+    fn test_schnorr_gadget() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameters = MySignature::setup_transparent(DEFAULT_DOMAIN);
+        let (pk, sk) = MySignature::keygen(&parameters, rng);
+        let (other_pk, _) = MySignature::keygen(&parameters, rng);
+        let message = Fq::rand(rng);
+        let signature = MySignature::sign(&parameters, &sk, &message, rng).unwrap();
+
+        let forged = Signature {
+            s: signature.s + Fr::from(1u64),
+            ..signature.clone()
+        };
+        let cases = [
+            (pk, message, signature.clone(), true),
+            (pk, message + Fq::from(1u64), signature.clone(), false),
+            (other_pk, message, signature.clone(), false),
+            (pk, message, forged, false),
+        ];
+        for (pk, message, signature, valid) in cases {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let parameters_var = ParametersVar::new_constant(cs.clone(), &parameters).unwrap();
+            let pk_var = PublicKeyVar::new_input(cs.clone(), || Ok(pk)).unwrap();
+            let message_var = FpVar::new_input(cs.clone(), || Ok(message)).unwrap();
+            let signature_var = SignatureVar::new_witness(cs.clone(), || Ok(&signature)).unwrap();
+            assert_eq!(signature, signature_var.value().unwrap());
+
+            let e = MyGadget::challenge(&signature_var.r, &pk_var, &message_var).unwrap();
+            assert_eq!(challenge::<EdwardsProjective>(&signature.r, &pk, &message).unwrap(), e.value().unwrap());
+
+            let result = MyGadget::verify(&parameters_var, &pk_var, &message_var, &signature_var).unwrap();
+            assert_eq!(valid, result.value().unwrap());
+            result.enforce_equal(&Boolean::TRUE).unwrap();
+            assert_eq!(valid, cs.is_satisfied().unwrap());
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::marker::PhantomData;
+use std::ops::Mul;
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField, ToConstraintField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::UniformRand;
+use rand::{CryptoRng, Rng};
+
+use crate::gadgets::hashes::poseidon::{self, PoseidonField};
+
+pub mod constraints;
+
+pub use crate::gadgets::public_encryptions::elgamal::{Parameters, PublicKey, SecretKey, DEFAULT_DOMAIN};
+
+/// Schnorr signatures on base field messages: `(R, s)` with `R = k*G`, `s = k + e*sk` and the
+/// challenge `e = Poseidon(R, pk, m)`.
+///
+/// The challenge lives in the base field, i.e. the constraint field of curve gadgets, so a
+/// circuit recomputes it natively; it is used as a scalar through its integer value.
+pub struct Schnorr<C: CurveGroup> {
+    _group: PhantomData<C>,
+}
+
+/// A message is one base field element, typically the hash of the signed data.
+pub type Message<C> = <C as CurveGroup>::BaseField;
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Signature<C: CurveGroup> {
+    pub r: C::Affine,
+    pub s: C::ScalarField,
+}
+
+/// `Poseidon(R, pk, m)`, with the points in their `ToConstraintField` encoding.
+pub fn challenge<C>(r: &C::Affine, pk: &PublicKey<C>, message: &Message<C>) -> Result<C::BaseField, ark_crypto_primitives::Error>
+where
+    C: CurveGroup,
+    C::BaseField: PoseidonField,
+    C::Affine: ToConstraintField<C::BaseField>,
+{
+    let encode = |p: &C::Affine| p.to_field_elements().ok_or("point has no field element encoding");
+    let mut inputs = encode(r)?;
+    inputs.extend(encode(pk)?);
+    inputs.push(*message);
+    poseidon::hash(&inputs)
+}
+
+// the challenge as a scalar, reduced the way a scalar multiplication by its bits reduces it
+fn challenge_scalar<C>(r: &C::Affine, pk: &PublicKey<C>, message: &Message<C>) -> Result<C::ScalarField, ark_crypto_primitives::Error>
+where
+    C: CurveGroup,
+    C::BaseField: PoseidonField,
+    C::Affine: ToConstraintField<C::BaseField>,
+{
+    let e = challenge::<C>(r, pk, message)?;
+    Ok(C::ScalarField::from_le_bytes_mod_order(&e.into_bigint().to_bytes_le()))
+}
+
+impl<C: CurveGroup> Schnorr<C>
+where
+    C::BaseField: PoseidonField,
+    C::Affine: ToConstraintField<C::BaseField>,
+{
+    /// Reproducible setup, as `ElGamal::setup_transparent`.
+    pub fn setup_transparent(domain: &[u8]) -> Parameters<C> {
+        Parameters::derive(domain)
+    }
+
+    pub fn setup<R: Rng>(rng: &mut R) -> Parameters<C> {
+        let generator = C::rand(rng).into();
+        Parameters { generator }
+    }
+
+    pub fn keygen<R: Rng>(pp: &Parameters<C>, rng: &mut R) -> (PublicKey<C>, SecretKey<C>) {
+        let secret_key = C::ScalarField::rand(rng);
+        let public_key = pp.generator.mul(secret_key).into();
+        (public_key, SecretKey(secret_key))
+    }
+
+    /// The nonce is drawn from `rng`; a repeated or predictable nonce reveals `sk`.
+    pub fn sign<R: Rng + CryptoRng>(
+        pp: &Parameters<C>,
+        sk: &SecretKey<C>,
+        message: &Message<C>,
+        rng: &mut R,
+    ) -> Result<Signature<C>, ark_crypto_primitives::Error> {
+        let pk = pp.generator.mul(sk.0).into_affine();
+        let k = C::ScalarField::rand(rng);
+        let r = pp.generator.mul(k).into_affine();
+
+        let e = challenge_scalar::<C>(&r, &pk, message)?;
+        Ok(Signature { r, s: k + e * sk.0 })
+    }
+
+    /// Checks `s*G = R + e*pk`.
+    pub fn verify(pp: &Parameters<C>, pk: &PublicKey<C>, message: &Message<C>, signature: &Signature<C>) -> bool {
+        let Ok(e) = challenge_scalar::<C>(&signature.r, pk, message) else {
+            return false;
+        };
+        pp.generator.mul(signature.s) == signature.r.into_group() + pk.mul(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::CurveGroup;
+    use ark_std::{test_rng, UniformRand};
+    use rand::{RngCore, SeedableRng};
+
+    use super::{Schnorr, Signature, DEFAULT_DOMAIN};
+
+    type C = ark_ed_on_bn254::EdwardsProjective;
+    type Fq = ark_ed_on_bn254::Fq;
+    type Fr = ark_ed_on_bn254::Fr;
+
+    #[test]
+    fn test_schnorr_signature() {
+        let rng = &mut ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+
+        let parameters = Schnorr::<C>::setup_transparent(DEFAULT_DOMAIN);
+        assert!(parameters.verify(DEFAULT_DOMAIN));
+        let (pk, sk) = Schnorr::<C>::keygen(&parameters, rng);
+        let (other_pk, _) = Schnorr::<C>::keygen(&parameters, rng);
+        let message = Fq::rand(rng);
+
+        let signature = Schnorr::<C>::sign(&parameters, &sk, &message, rng).unwrap();
+        assert!(Schnorr::<C>::verify(&parameters, &pk, &message, &signature));
+
+        assert!(!Schnorr::<C>::verify(&parameters, &pk, &(message + Fq::from(1u64)), &signature));
+        assert!(!Schnorr::<C>::verify(&parameters, &other_pk, &message, &signature));
+
+        let forged_s = Signature {
+            s: signature.s + Fr::from(1u64),
+            ..signature.clone()
+        };
+        assert!(!Schnorr::<C>::verify(&parameters, &pk, &message, &forged_s));
+
+        let forged_r = Signature {
+            r: (signature.r + parameters.generator).into_affine(),
+            ..signature.clone()
+        };
+        assert!(!Schnorr::<C>::verify(&parameters, &pk, &message, &forged_r));
+
+        // signatures are randomized
+        let again = Schnorr::<C>::sign(&parameters, &sk, &message, rng).unwrap();
+        assert_ne!(signature, again);
+        assert!(Schnorr::<C>::verify(&parameters, &pk, &message, &again));
+
+        // parameters from `setup` work the same way
+        let parameters = Schnorr::<C>::setup(rng);
+        let (pk, sk) = Schnorr::<C>::keygen(&parameters, rng);
+        let signature = Schnorr::<C>::sign(&parameters, &sk, &message, rng).unwrap();
+        assert!(Schnorr::<C>::verify(&parameters, &pk, &message, &signature));
+        assert!(!signature.r.is_zero());
+    }
+}